@@ -0,0 +1,111 @@
+use std::fmt;
+use serde::Serialize;
+use crate::dictionary;
+use crate::error::WordleError;
+use crate::file::Round;
+use crate::matrix::FeedbackMatrix;
+use crate::replay;
+use crate::session::Status;
+use crate::solver;
+
+//一次猜测的分析，均针对猜测时第一个未完成的棋盘
+//entropy 为玩家猜测的期望信息量，best_entropy 为求解器单步最优猜测的期望信息量，bits 为实际得到的信息量
+//skill 为 entropy 占 best_entropy 的百分比，luck 为实际比期望多得到的信息量
+#[derive(Debug, Clone, Serialize)]
+pub struct StepAnalysis
+{
+    pub guess: String,
+    pub candidates: usize,
+    pub remaining: usize,
+    pub entropy: f64,
+    pub best_guess: String,
+    pub best_entropy: f64,
+    pub bits: f64,
+    pub skill: f64,
+    pub luck: f64,
+}
+
+//一局的分析，result 为 CORRECT、FAILED 或 UNFINISHED，skill 为各步的平均值，luck 为各步之和
+#[derive(Debug, Clone, Serialize)]
+pub struct Analysis
+{
+    pub answers: Vec<String>,
+    pub result: String,
+    pub steps: Vec<StepAnalysis>,
+    pub skill: f64,
+    pub luck: f64,
+}
+
+//按存档中的答案逐次重新计算，候选答案与信息熵来自 matrix
+pub fn analyze(round: &Round, guess_file: &[String], max_guesses: usize, matrix: &FeedbackMatrix) -> Result<Analysis, WordleError>
+{
+    let mut session = replay::new_session(round, guess_file, max_guesses)?;
+    let mut steps = Vec::new();
+    for guess in round.guesses.iter()
+    {
+        let board = match session.boards().iter().position(|board| !board.is_over())
+        {
+            Some(board) => board,
+            None => return Err(WordleError::invalid_guess(guess, "GAME OVER")),
+        };
+        let before = &session.boards()[board];
+        let possible_answers = matrix.candidates(before.guesses(), before.results());
+        let (info, _) = solver::help(matrix, before.guesses(), before.results());
+        let (best_guess, best_entropy) = info.into_iter().next().unwrap_or_default();
+        let entropy = solver::guess_entropy(matrix, &dictionary::uppercase(guess), &possible_answers);
+
+        session.submit_guess(guess)?;
+        let after = &session.boards()[board];
+        let remaining = matrix.candidates(after.guesses(), after.results()).len();
+        let bits = if remaining > 0 && !possible_answers.is_empty()
+        {
+            (possible_answers.len() as f64 / remaining as f64).log2()
+        }
+        else
+        {
+            0.0
+        };
+        steps.push(StepAnalysis
+        {
+            guess: after.guesses().last().cloned().unwrap_or_default(),
+            candidates: possible_answers.len(),
+            remaining,
+            entropy,
+            best_guess,
+            best_entropy,
+            bits,
+            skill: if best_entropy > 0.0 { (entropy / best_entropy * 100.0).min(100.0) } else { 100.0 },
+            luck: bits - entropy,
+        });
+    }
+    let skill = if steps.is_empty() { 0.0 } else { steps.iter().map(|step| step.skill).sum::<f64>() / steps.len() as f64 };
+    let luck = steps.iter().map(|step| step.luck).sum();
+    Ok(Analysis
+    {
+        answers: round.all_answers().to_vec(),
+        result: match session.status()
+        {
+            Status::Won => "CORRECT",
+            Status::Lost => "FAILED",
+            Status::Playing => "UNFINISHED",
+        }.to_string(),
+        steps,
+        skill,
+        luck,
+    })
+}
+
+//表格形式，每次猜测一行，最后一行为总评
+impl fmt::Display for Analysis
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "{:<9}{:>6}{:>6}{:>8}{:>8}  {:<9}{:>8}{:>7}{:>7}", "GUESS", "LEFT", "AFTER", "BITS", "ENTROPY", "BEST", "ENTROPY", "SKILL", "LUCK")?;
+        for step in self.steps.iter()
+        {
+            writeln!(f, "{:<9}{:>6}{:>6}{:>8.2}{:>8.2}  {:<9}{:>8.2}{:>7.0}{:>+7.2}",
+                step.guess, step.candidates, step.remaining, step.bits, step.entropy, step.best_guess, step.best_entropy, step.skill, step.luck)?;
+        }
+        write!(f, "{} {}  SKILL {:.0}  LUCK {:+.2}", self.answers.join(" "), self.result, self.skill, self.luck)
+    }
+}
@@ -1,343 +1,535 @@
-use std::collections::{HashMap, HashSet};
-use crate::builtin_words::FINAL;
-use crate::file;
+use clap::{Args, Parser, Subcommand};
+use wordle::builtin_words::{ACCEPTABLE, FINAL};
+use wordle::feedback::{DEFAULT_LENGTH, MAX_LENGTH};
+use wordle::config::Layers;
+use wordle::dictionary::{Dictionary, DEFAULT_DICTIONARY};
+use wordle::error::WordleError;
+use wordle::file::Config;
+use wordle::hard_mode::Difficulty;
+use wordle::session::DEFAULT_MAX_GUESSES;
+use wordle::share::ShareFormat;
+use wordle::theme::Theme;
+use wordle::wordlist::{self, WordList};
 
-pub struct Command
+//命令行：不带子命令时与 play 相同，保持原有的参数用法
+#[derive(Parser, Debug)]
+#[command(name = "wordle", version, about = "Wordle in the terminal, with a solver and replays of saved games")]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli
 {
-    pub mode: HashSet<String>,
-    pub value: HashMap<String, u64>,
-    pub info: HashMap<String, String>,
-    pub answer_file: Vec<String>,
-    pub guess_file: Vec<String>,
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
+    #[command(flatten)]
+    pub play: PlayArgs,
 }
 
-pub fn process_arg() -> Result<Command, String>
+#[derive(Subcommand, Debug)]
+pub enum Commands
 {
-    let mut args: HashSet<String> = HashSet::new();
-    let mut argv: HashMap<String, u64> = HashMap::new();
-    let mut argw: HashMap<String, String> = HashMap::new();
-    let mut arg: Vec<String> = Vec::new();
-    let mut answer: Vec<String> = Vec::new();
-    let mut guess: Vec<String> = Vec::new();
-    let mut iter = std::env::args();
-    iter.next();
-    while let Some(tmp) = iter.next()
+    #[command(about = "Play a game (the default when no subcommand is given)")]
+    Play(PlayArgs),
+    #[command(about = "Suggest guesses for a game played elsewhere")]
+    Solve(SolveArgs),
+    #[command(about = "Let the solver play every answer and report the average number of guesses")]
+    Bench
     {
-        if tmp == "-c" || tmp == "--config"
-        {
-            if let Some(config) = iter.next()
-            {
-                if let Some(i) = config.chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        match file::read_config(&config)
-                        {
-                            Ok(tmp_config) =>
-                            {
-                                if let Some(set) = tmp_config.random
-                                {
-                                    if set
-                                    {
-                                        args.insert("random".to_string());
-                                    }
-                                }
-                                if let Some(set) = tmp_config.difficult
-                                {
-                                    if set
-                                    {
-                                        args.insert("difficult".to_string());
-                                    }
-                                }
-                                if let Some(set) = tmp_config.stats
-                                {
-                                    if set
-                                    {
-                                        args.insert("stats".to_string());
-                                    }
-                                }
-                                if let Some(set) = tmp_config.day
-                                {
-                                    args.insert("day".to_string());
-                                    argv.insert("day".to_string(), set);
-                                }
-                                if let Some(set) = tmp_config.seed
-                                {
-                                    args.insert("seed".to_string());
-                                    argv.insert("seed".to_string(), set);
-                                }
-                                if let Some(set) = tmp_config.final_set
-                                {
-                                    args.insert("final_set".to_string());
-                                    argw.insert("final_set".to_string(), set);
-                                }
-                                if let Some(set) = tmp_config.acceptable_set
-                                {
-                                    args.insert("acceptable_set".to_string());
-                                    argw.insert("acceptable_set".to_string(), set);
-                                }
-                                if let Some(set) = tmp_config.state
-                                {
-                                    args.insert("state".to_string());
-                                    argw.insert("state".to_string(), set);
-                                }
-                                if let Some(set) = tmp_config.word
-                                {
-                                    args.insert("word".to_string());
-                                    argw.insert("word".to_string(), set);
-                                }
-                            }
-                            Err(_) => return Err("INVALID CONFIG".to_string())
-                        }
-                    }
-                    else
-                    {
-                        return Err("INVALID COMMAND LINE".to_string());
-                    }
-                }
-            }
-            else
-            {
-                return Err("INVALID COMMAND LINE".to_string());
-            }
-        }
-        else
+        #[arg(short, long, default_value_t = DEFAULT_MAX_GUESSES, help = "Guesses allowed per game")]
+        max_guesses: usize,
+        #[arg(long, help = "default, high-contrast, colour-blind or monochrome")]
+        theme: Option<Theme>,
+    },
+    #[command(about = "Print the statistics of a state file")]
+    Stats
+    {
+        #[arg(help = "State file written by --state")]
+        state: String,
+    },
+    #[command(about = "List the games in a state file, or replay one guess by guess")]
+    Replay(SavedArgs),
+    #[command(about = "Compare the guesses of a saved game with the solver")]
+    Analyze
+    {
+        #[command(flatten)]
+        saved: SavedArgs,
+        #[arg(long, help = "Print the analysis as JSON")]
+        json: bool,
+    },
+    #[command(about = "Inspect the configuration")]
+    Config
+    {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    #[command(about = "Work with word lists")]
+    Wordlist
+    {
+        #[command(subcommand)]
+        command: WordlistCommand,
+    },
+    #[command(about = "List and inspect dictionaries")]
+    Dictionary
+    {
+        #[command(subcommand)]
+        command: DictionaryCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DictionaryCommand
+{
+    #[command(about = "Print the name and description of every dictionary")]
+    List,
+    #[command(about = "Print the alphabet, keyboard layout and word lists of a dictionary")]
+    Show
+    {
+        #[arg(default_value = DEFAULT_DICTIONARY, help = "Dictionary name")]
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand
+{
+    #[command(about = "Print the effective settings and where each one comes from")]
+    Show(PlayArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WordlistCommand
+{
+    #[command(visible_alias = "export", about = "Print the answer list, or the acceptable list with --acceptable")]
+    Show
+    {
+        #[command(flatten)]
+        words: WordListArgs,
+        #[arg(long, help = "Print the acceptable guesses instead of the answers")]
+        acceptable: bool,
+        #[arg(short, long, value_name = "FILE", help = "Write the list to a file instead of stdout")]
+        output: Option<String>,
+    },
+    #[command(about = "Report bad lines in the word lists and answers missing from the acceptable list")]
+    Check
+    {
+        #[command(flatten)]
+        words: WordListArgs,
+    },
+    #[command(about = "Print the words only in the first list (-) and only in the second list (+)")]
+    Diff
+    {
+        #[arg(help = "Word list file, or builtin:final / builtin:acceptable")]
+        first: String,
+        #[arg(help = "Word list file, or builtin:final / builtin:acceptable")]
+        second: String,
+        #[arg(short, long, help = "Only keep words of this length")]
+        length: Option<usize>,
+    },
+    #[command(about = "Print the words in any of the lists")]
+    Merge
+    {
+        #[arg(required = true, help = "Word list files, or builtin:final / builtin:acceptable")]
+        lists: Vec<String>,
+        #[command(flatten)]
+        output: ListOutputArgs,
+    },
+    #[command(about = "Print the words of a list that are in none of the other lists")]
+    Subtract
+    {
+        #[arg(help = "Word list file, or builtin:final / builtin:acceptable")]
+        list: String,
+        #[arg(required = true, help = "Lists of words to remove")]
+        remove: Vec<String>,
+        #[command(flatten)]
+        output: ListOutputArgs,
+    },
+    #[command(about = "Print the words of a list that match every given condition")]
+    Filter
+    {
+        #[arg(help = "Word list file, or builtin:final / builtin:acceptable")]
+        list: String,
+        #[arg(long, help = "Regular expression, case-insensitive")]
+        regex: Option<String>,
+        #[arg(long, help = "Letters by position, ? for any letter, e.g. c?a?e")]
+        pattern: Option<String>,
+        #[arg(long, value_name = "LETTERS", help = "Letters every word must contain")]
+        contains: Option<String>,
+        #[arg(long, value_name = "LETTERS", help = "Letters no word may contain")]
+        excludes: Option<String>,
+        #[command(flatten)]
+        output: ListOutputArgs,
+    },
+    #[command(about = "Print letter frequencies overall and by position")]
+    Stats
+    {
+        #[arg(help = "Word list file, or builtin:final / builtin:acceptable")]
+        list: String,
+        #[arg(short, long, help = "Only keep words of this length")]
+        length: Option<usize>,
+    },
+}
+
+//词典、词库与单词长度，游戏、回放与词库子命令共用
+#[derive(Args, Debug, Clone, Default)]
+pub struct WordListArgs
+{
+    #[arg(long, value_name = "NAME", help = "Dictionary: en (builtin) or a directory in $WORDLE_DATA_DIR/dictionaries")]
+    pub dictionary: Option<String>,
+    #[arg(short, long, value_name = "FILE", help = "Answer list, one word per line")]
+    pub final_set: Option<String>,
+    #[arg(short, long, value_name = "FILE", help = "Acceptable guesses, one word per line")]
+    pub acceptable_set: Option<String>,
+    #[arg(short, long, help = "Word length from 1 to 8; other than 5 needs both word lists")]
+    pub length: Option<usize>,
+}
+
+//词库工具的输出：单词长度与输出文件
+#[derive(Args, Debug, Clone, Default)]
+pub struct ListOutputArgs
+{
+    #[arg(short, long, help = "Only keep words of this length")]
+    pub length: Option<usize>,
+    #[arg(short, long, value_name = "FILE", help = "Write the list to a file instead of stdout")]
+    pub output: Option<String>,
+}
+
+//游戏的参数，未给出的项可以由配置文件补充
+#[derive(Args, Debug, Clone, Default)]
+pub struct PlayArgs
+{
+    #[arg(short, long, help = "Answer of the game, comma-separated with several boards")]
+    pub word: Option<String>,
+    #[arg(short, long, overrides_with = "no_random", help = "Pick the answer from a seeded shuffle")]
+    pub random: bool,
+    #[arg(long, overrides_with = "random", help = "Turn off --random")]
+    pub no_random: bool,
+    #[arg(short = 'D', long, overrides_with = "no_difficult", help = "Hard mode: keep every green and yellow")]
+    pub difficult: bool,
+    #[arg(long, overrides_with = "difficult", help = "Turn off --difficult")]
+    pub no_difficult: bool,
+    #[arg(short = 'U', long, overrides_with = "no_ultra_hard", help = "Ultra hard mode: no grey letters or known-wrong yellows either")]
+    pub ultra_hard: bool,
+    #[arg(long, overrides_with = "ultra_hard", help = "Turn off --ultra-hard")]
+    pub no_ultra_hard: bool,
+    #[arg(short = 't', long, overrides_with = "no_stats", help = "Print statistics after each round")]
+    pub stats: bool,
+    #[arg(long, overrides_with = "stats", help = "Turn off --stats")]
+    pub no_stats: bool,
+    #[arg(short, long, help = "Day of the random answer, starting from 1")]
+    pub day: Option<u64>,
+    #[arg(short, long, help = "Seed of the random shuffle")]
+    pub seed: Option<u64>,
+    #[arg(short = 'S', long, value_name = "FILE", help = "Load and save the game state")]
+    pub state: Option<String>,
+    #[arg(short, long, value_name = "FILE", help = "Config file in TOML or JSON; environment variables and the command line take precedence")]
+    pub config: Option<String>,
+    #[command(flatten)]
+    pub words: WordListArgs,
+    #[arg(short, long, help = "Guesses allowed per game")]
+    pub max_guesses: Option<usize>,
+    #[arg(short, long, help = "Boards played at once: 2 for Dordle, 4 for Quordle")]
+    pub boards: Option<usize>,
+    #[arg(short = 'A', long, overrides_with = "no_absurdle", help = "Absurdle: the answer is picked adversarially")]
+    pub absurdle: bool,
+    #[arg(long, overrides_with = "absurdle", help = "Turn off --absurdle")]
+    pub no_absurdle: bool,
+    #[arg(long, help = "default, high-contrast, colour-blind or monochrome")]
+    pub theme: Option<Theme>,
+    #[arg(long, overrides_with = "animation", help = "Turn off TUI animations")]
+    pub no_animation: bool,
+    #[arg(long, overrides_with = "no_animation", help = "Turn TUI animations back on")]
+    pub animation: bool,
+    #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "emoji", help = "Print a shareable grid after each round: emoji or ascii")]
+    pub share: Option<ShareFormat>,
+}
+
+//回放与分析的参数
+#[derive(Args, Debug, Clone)]
+pub struct SavedArgs
+{
+    #[arg(help = "State file written by --state")]
+    pub state: String,
+    #[arg(help = "Game number, starting from 1")]
+    pub game: Option<usize>,
+    #[command(flatten)]
+    pub words: WordListArgs,
+    #[arg(short, long, help = "Guesses allowed per game")]
+    pub max_guesses: Option<usize>,
+    #[arg(long, help = "default, high-contrast, colour-blind or monochrome")]
+    pub theme: Option<Theme>,
+    #[arg(long, help = "Turn off TUI animations")]
+    pub no_animation: bool,
+}
+
+impl SavedArgs
+{
+    //回放时使用的游戏设置
+    pub fn play_args(&self) -> PlayArgs
+    {
+        PlayArgs
         {
-            arg.push(tmp.clone());
+            state: Some(self.state.clone()),
+            words: self.words.clone(),
+            max_guesses: self.max_guesses,
+            theme: self.theme,
+            no_animation: self.no_animation,
+            ..PlayArgs::default()
         }
     }
-    let mut count: usize = 0;
-    while count < arg.len()
+}
+
+//求解器的参数：词典、词库与单词长度决定可能的答案
+#[derive(Args, Debug, Clone)]
+pub struct SolveArgs
+{
+    #[command(flatten)]
+    pub words: WordListArgs,
+    #[arg(short, long, help = "Guesses allowed per game")]
+    pub max_guesses: Option<usize>,
+}
+
+impl SolveArgs
+{
+    //求解时使用的游戏设置
+    pub fn play_args(&self) -> PlayArgs
     {
-        match &arg[count]
+        PlayArgs
         {
-            a if a == "-w" || a == "--word" =>
-            {
-                args.insert("word".to_string());
-                if count >= arg.len() - 1
-                {
-                    break;
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argw.insert("word".to_string(), arg[count + 1].to_string());
-                        count += 1;
-                    }
-                }
-            }
-            b if b == "-r" || b == "--random" =>
-            {
-                args.insert("random".to_string());
-            }
-            c if c == "-D" || c == "--difficult" =>
-            {
-                args.insert("difficult".to_string());
-            }
-            d if d == "-t" || d == "--stats" =>
-            {
-                args.insert("stats".to_string());
-            }
-            e if e == "-d" || e == "--day" =>
-            {
-                args.insert("day".to_string());
-                if count >= arg.len() - 1
-                {
-                    break;
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argv.insert("day".to_string(), arg[count + 1].parse().unwrap());
-                        count += 1;
-                    }
-                }
-            }
-            f if f == "-s" || f == "--seed" =>
-            {
-                args.insert("seed".to_string());
-                if count >= arg.len() - 1
-                {
-                    break;
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argv.insert("seed".to_string(), arg[count + 1].parse().unwrap());
-                        count += 1;
-                    }
-                }
-            }
-            g if g == "-f" || g == "--final-set" =>
-            {
-                args.insert("final_set".to_string());
-                if count >= arg.len() - 1
-                {
-                    return Err("INVALID COMMAND LINE".to_string());
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argw.insert("final_set".to_string(), arg[count + 1].clone());
-                        count += 1;
-                    }
-                    else
-                    {
-                        return Err("INVALID COMMAND LINE".to_string());
-                    }
-                }
-            }
-            h if h == "-a" || h == "--acceptable-set" =>
-            {
-                args.insert("acceptable_set".to_string());
-                if count >= arg.len() - 1
-                {
-                    return Err("INVALID COMMAND LINE".to_string());
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argw.insert("acceptable_set".to_string(), arg[count + 1].clone());
-                        count += 1;
-                    }
-                    else
-                    {
-                        return Err("INVALID COMMAND LINE".to_string());
-                    }
-                }
-            }
-            i if i == "-S" || i == "--state" =>
-            {
-                args.insert("state".to_string());
-                if count >= arg.len() - 1
-                {
-                    return Err("INVALID COMMAND LINE".to_string());
-
-                }
-                if let Some(i) = arg[count + 1].chars().nth(0)
-                {
-                    if i != '-'
-                    {
-                        argw.insert("state".to_string(), arg[count + 1].clone());
-                        count += 1;
-                    }
-                    else
-                    {
-                        return Err("INVALID COMMAND LINE".to_string());
-                    }
-                }
-            }
-            _ =>
-            {
-                println!("HERE");
-                return Err("INVALID COMMAND LINE".to_string());
-            }
+            words: self.words.clone(),
+            max_guesses: self.max_guesses,
+            ..PlayArgs::default()
         }
-        count += 1;
     }
-    if let Some(file) = argw.get("acceptable_set")
+}
+
+//合并命令行与配置文件后的设置
+pub struct Command
+{
+    pub word: Option<String>,
+    pub random: bool,
+    pub difficulty: Difficulty,
+    pub stats: bool,
+    pub day: Option<u64>,
+    pub seed: Option<u64>,
+    pub state: Option<String>,
+    pub absurdle: bool,
+    pub no_animation: bool,
+    pub share: Option<ShareFormat>,
+    pub dictionary: Dictionary,
+    pub answer_file: Vec<String>,
+    pub guess_file: Vec<String>,
+    pub length: usize,
+    pub max_guesses: usize,
+    pub boards: usize,
+    pub theme: Theme,
+}
+
+impl PlayArgs
+{
+    //命令行中给出的项，开关及其否定形式都没有给出时为 None
+    pub fn to_config(&self) -> Config
     {
-        match file::read_txt(&file)
+        let flag = |on: bool, off: bool| if on { Some(true) } else if off { Some(false) } else { None };
+        Config
         {
-            Ok(tmp) => guess = tmp,
-            Err(_) =>
-            {
-                return Err("INVALID A SET".to_string());
-            }
+            word: self.word.clone(),
+            random: flag(self.random, self.no_random),
+            difficult: flag(self.difficult, self.no_difficult),
+            ultra_hard: flag(self.ultra_hard, self.no_ultra_hard),
+            stats: flag(self.stats, self.no_stats),
+            day: self.day,
+            seed: self.seed,
+            state: self.state.clone(),
+            dictionary: self.words.dictionary.clone(),
+            final_set: self.words.final_set.clone(),
+            acceptable_set: self.words.acceptable_set.clone(),
+            length: self.words.length.map(|length| length as u64),
+            max_guesses: self.max_guesses.map(|max_guesses| max_guesses as u64),
+            boards: self.boards.map(|boards| boards as u64),
+            absurdle: flag(self.absurdle, self.no_absurdle),
+            theme: self.theme.map(|theme| theme.name().to_string()),
+            no_animation: flag(self.no_animation, self.animation),
+            share: self.share.map(|share| share.to_string()),
         }
     }
-    if let Some(file) = argw.get("final_set")
+
+    //依次合并默认值、用户配置文件、-c 指定的文件、环境变量与命令行
+    pub fn layers(&self) -> Result<Layers, WordleError>
     {
-        match file::read_txt(&file)
+        Layers::load(self.config.as_deref(), &self.to_config())
+    }
+}
+
+impl WordListArgs
+{
+    //选用的词典，未指定时为内置的 en
+    pub fn dictionary(&self) -> Result<Dictionary, WordleError>
+    {
+        Dictionary::open(self.dictionary.as_deref().unwrap_or(DEFAULT_DICTIONARY))
+    }
+
+    //答案与候选词库的文件，-f 与 -a 优先于词典中的词库，都没有时为 None，即内置词库
+    pub fn sets(&self, dictionary: &Dictionary) -> (Option<String>, Option<String>)
+    {
+        (
+            self.final_set.clone().or_else(|| dictionary.final_set.clone()),
+            self.acceptable_set.clone().or_else(|| dictionary.acceptable_set.clone()),
+        )
+    }
+
+    //单词长度，内置词库只有默认长度的单词
+    pub fn length(&self, dictionary: &Dictionary) -> Result<usize, WordleError>
+    {
+        let length = self.length.unwrap_or(DEFAULT_LENGTH);
+        if length == 0 || length > MAX_LENGTH
         {
-            Ok(tmp) => answer = tmp,
-            Err(_) =>
-            {
-                return Err("INVALID F SET".to_string());
-            }
+            return Err(WordleError::bad_argument("--length", &format!("MUST BE FROM 1 TO {}", MAX_LENGTH)));
         }
+        if length != DEFAULT_LENGTH && !matches!(self.sets(dictionary), (Some(_), Some(_)))
+        {
+            return Err(WordleError::bad_argument("--length", "NEEDS BOTH --final-set AND --acceptable-set"));
+        }
+        Ok(length)
     }
-    Ok(Command
+
+    //单词长度与两个词库，词库中长度不符的单词被过滤，过滤后为空则拒绝
+    //返回 (长度, 答案词库, 候选词库)，未指定的词库为空
+    pub fn load(&self, dictionary: &Dictionary) -> Result<(usize, Vec<String>, Vec<String>), WordleError>
+    {
+        let length = self.length(dictionary)?;
+        let (final_set, acceptable_set) = self.sets(dictionary);
+        let guess = match &acceptable_set
         {
-            mode: args,
-            value: argv,
-            info: argw,
-            answer_file: answer,
-            guess_file: guess,
-        })
+            Some(file) => read_list(file, length, &dictionary.alphabet)?,
+            None => Vec::new(),
+        };
+        let answer = match &final_set
+        {
+            Some(file) => read_list(file, length, &dictionary.alphabet)?,
+            None => Vec::new(),
+        };
+        Ok((length, answer, guess))
+    }
 }
 
-pub fn arg_is_valid(cmd: &Command) -> bool
+impl Command
 {
-    if let Some(_i) = cmd.mode.get("final_set")
+    //读取配置文件与词库，检查各项取值
+    pub fn new(args: PlayArgs) -> Result<Command, WordleError>
     {
-        if let None = cmd.info.get("final_set")
+        let config = args.layers()?.config()?;
+        let words = WordListArgs
         {
-            return false;
+            dictionary: config.dictionary,
+            final_set: config.final_set,
+            acceptable_set: config.acceptable_set,
+            length: config.length.map(|length| length as usize),
+        };
+        let dictionary = words.dictionary()?;
+        let (length, answer_file, guess_file) = words.load(&dictionary)?;
+
+        //每局最多猜测次数
+        let max_guesses = config.max_guesses.map_or(DEFAULT_MAX_GUESSES, |max_guesses| max_guesses as usize);
+        if max_guesses == 0
+        {
+            return Err(WordleError::bad_argument("--max-guesses", "MUST BE AT LEAST 1"));
         }
-    }
-    if let Some(_i) = cmd.mode.get("acceptable_set")
-    {
-        if let None = cmd.info.get("acceptable_set")
+
+        //同时进行的棋盘数，Dordle 为 2，Quordle 为 4，Octordle 为 8
+        let boards = config.boards.map_or(1, |boards| boards as usize);
+        if boards == 0
         {
-            return false;
+            return Err(WordleError::bad_argument("--boards", "MUST BE AT LEAST 1"));
         }
-        if let Some(_j) = cmd.mode.get("final_set")
+
+        let difficulty = if config.ultra_hard.unwrap_or(false)
         {
-            if !cmd.answer_file.is_empty()
-            {
-                for check in cmd.answer_file.iter()
-                {
-                    if !cmd.guess_file.contains(check)
-                    {
-                        return false;
-                    }
-                }
-            }
+            Difficulty::UltraHard
+        }
+        else if config.difficult.unwrap_or(false)
+        {
+            Difficulty::Hard
         }
         else
         {
-            for check in FINAL
+            Difficulty::Normal
+        };
+
+        let theme = config.theme
+            .map(|theme| theme.parse())
+            .transpose()
+            .map_err(|reason: String| WordleError::bad_argument("--theme", &reason))?
+            .unwrap_or_default();
+        let share = config.share
+            .map(|share| share.parse())
+            .transpose()
+            .map_err(|reason: String| WordleError::bad_argument("--share", &reason))?;
+
+        Ok(Command
             {
-                if !cmd.guess_file.contains(&check.to_string())
-                {
-                    return false;
-                }
-            }   
-        }
+                word: config.word,
+                random: config.random.unwrap_or(false),
+                difficulty,
+                stats: config.stats.unwrap_or(false),
+                day: config.day,
+                seed: config.seed,
+                state: config.state,
+                absurdle: config.absurdle.unwrap_or(false),
+                no_animation: config.no_animation.unwrap_or(false),
+                //结束后输出可分享的结果
+                share,
+                //字母表与键盘布局
+                dictionary,
+                answer_file,
+                guess_file,
+                length,
+                max_guesses,
+                boards,
+                //配色方案，NO_COLOR 已作为默认值之上的一层合并到配置中
+                theme,
+            })
     }
-    if let Some(_i) = cmd.mode.get("state")
+}
+
+//读取词库并只保留长度为 length 的单词，有字母表以外的字母、不合法的行或过滤后为空则拒绝
+fn read_list(file: &str, length: usize, alphabet: &str) -> Result<Vec<String>, WordleError>
+{
+    let list = WordList::read_in(file, Some(length), alphabet)?;
+    list.validate()?;
+    Ok(list.words)
+}
+
+//检查参数之间的冲突
+pub fn check_logic(cmd: &Command) -> Result<(), WordleError>
+{
+    //每个答案都必须是可以猜测的单词，未指定的词库使用内置词库
+    if !cmd.guess_file.is_empty() || !cmd.answer_file.is_empty()
     {
-        if let None = cmd.info.get("state")
+        let answers = if cmd.answer_file.is_empty() { WordList::builtin("builtin", FINAL).words } else { cmd.answer_file.clone() };
+        let guesses = if cmd.guess_file.is_empty() { WordList::builtin("builtin", ACCEPTABLE).words } else { cmd.guess_file.clone() };
+        let missing = wordlist::subtract(&answers, &guesses);
+        if !missing.is_empty()
         {
-            return false;
+            let flag = if cmd.answer_file.is_empty() { "--acceptable-set" } else { "--final-set" };
+            let mut reason = format!("MISSING FROM THE ACCEPTABLE SET ({}): {}", missing.len(), missing.iter().take(5).cloned().collect::<Vec<String>>().join(" "));
+            if missing.len() > 5
+            {
+                reason.push_str(" ...");
+            }
+            return Err(WordleError::bad_argument(flag, &reason));
         }
     }
-    if let Some(_i) = cmd.mode.get("random")
+    if cmd.random && cmd.word.is_some()
     {
-        if let Some(_j) = cmd.mode.get("word")
-        {
-            return false;
-        }
+        return Err(WordleError::bad_argument("--word", "CANNOT BE USED WITH --random"));
     }
-    if let Some(_i) = cmd.mode.get("day")
+    if cmd.absurdle && (cmd.word.is_some() || cmd.random || cmd.boards > 1)
     {
-        if let None = cmd.mode.get("random")
-        {
-            return false;
-        }
+        return Err(WordleError::bad_argument("--absurdle", "CANNOT BE USED WITH --word, --random OR --boards"));
     }
-    if let Some(_i) = cmd.mode.get("seed")
+    if (cmd.day.is_some() || cmd.seed.is_some()) && !cmd.random
     {
-        if let None = cmd.mode.get("random")
-        {
-            return false;
-        }
+        let flag = if cmd.day.is_some() { "--day" } else { "--seed" };
+        return Err(WordleError::bad_argument(flag, "NEEDS --random"));
     }
-    true
-}
\ No newline at end of file
+    Ok(())
+}
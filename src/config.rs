@@ -0,0 +1,244 @@
+use std::fmt;
+use std::path::PathBuf;
+use serde_json::{Map, Value};
+use crate::dictionary::DEFAULT_DICTIONARY;
+use crate::error::WordleError;
+use crate::feedback::DEFAULT_LENGTH;
+use crate::file::{self, Config};
+use crate::session::DEFAULT_MAX_GUESSES;
+
+//配置项的来源，按优先级从低到高：内置默认值、用户配置文件、-c 指定的文件、环境变量、命令行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source
+{
+    Default,
+    UserFile(PathBuf),
+    ConfigFile(PathBuf),
+    Env(String),
+    CommandLine,
+}
+
+impl fmt::Display for Source
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Source::Default => write!(f, "default"),
+            Source::UserFile(path) | Source::ConfigFile(path) => write!(f, "{}", path.display()),
+            Source::Env(name) => write!(f, "${}", name),
+            Source::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+//配置项的类型，用于解析环境变量
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind
+{
+    Bool,
+    Number,
+    Text,
+}
+
+//全部配置项，与 Config 的字段一一对应
+const KEYS: [(&str, Kind); 18] =
+[
+    ("word", Kind::Text),
+    ("random", Kind::Bool),
+    ("difficult", Kind::Bool),
+    ("ultra_hard", Kind::Bool),
+    ("stats", Kind::Bool),
+    ("day", Kind::Number),
+    ("seed", Kind::Number),
+    ("state", Kind::Text),
+    ("dictionary", Kind::Text),
+    ("final_set", Kind::Text),
+    ("acceptable_set", Kind::Text),
+    ("length", Kind::Number),
+    ("max_guesses", Kind::Number),
+    ("boards", Kind::Number),
+    ("absurdle", Kind::Bool),
+    ("theme", Kind::Text),
+    ("no_animation", Kind::Bool),
+    ("share", Kind::Text),
+];
+
+//逐层合并的配置，每一项记录取值与来源，后加入的层覆盖之前的层
+#[derive(Debug, Clone)]
+pub struct Layers
+{
+    values: Vec<Option<(Value, Source)>>,
+}
+
+impl Default for Layers
+{
+    fn default() -> Layers
+    {
+        Layers{ values: vec![None; KEYS.len()] }
+    }
+}
+
+impl Layers
+{
+    //按优先级依次读取各层，file 为 -c 指定的文件，cli 为命令行中给出的项
+    pub fn load(file: Option<&str>, cli: &Config) -> Result<Layers, WordleError>
+    {
+        let mut layers = Layers::default();
+        layers.apply(&defaults(), Source::Default);
+        layers.apply_no_color(|name| std::env::var(name).ok());
+        if let Some(path) = user_file()
+        {
+            layers.apply(&file::read_config(&path.to_string_lossy())?, Source::UserFile(path));
+        }
+        if let Some(file) = file
+        {
+            layers.apply(&file::read_config(file)?, Source::ConfigFile(PathBuf::from(file)));
+        }
+        layers.apply_env(|name| std::env::var(name).ok())?;
+        layers.apply(cli, Source::CommandLine);
+        Ok(layers)
+    }
+
+    //用 config 中给出的项覆盖当前取值
+    pub fn apply(&mut self, config: &Config, source: Source)
+    {
+        let map = match serde_json::to_value(config)
+        {
+            Ok(Value::Object(map)) => map,
+            _ => return,
+        };
+        for (i, (key, _)) in KEYS.iter().enumerate()
+        {
+            match map.get(*key)
+            {
+                Some(Value::Null) | None => {}
+                Some(value) => self.values[i] = Some((value.clone(), source.clone())),
+            }
+        }
+    }
+
+    //NO_COLOR 非空时默认使用黑白主题，只覆盖内置默认值，配置文件等指定的主题优先
+    pub fn apply_no_color<F: Fn(&str) -> Option<String>>(&mut self, var: F)
+    {
+        if var("NO_COLOR").is_some_and(|value| !value.is_empty())
+        {
+            self.values[index("theme")] = Some((Value::from("monochrome"), Source::Env("NO_COLOR".to_string())));
+        }
+    }
+
+    //读取 WORDLE_<KEY> 形式的环境变量，var 按名称返回变量的值，空值视为未设置
+    pub fn apply_env<F: Fn(&str) -> Option<String>>(&mut self, var: F) -> Result<(), WordleError>
+    {
+        for (i, (key, kind)) in KEYS.iter().enumerate()
+        {
+            let name = format!("WORDLE_{}", key.to_uppercase());
+            let text = match var(&name)
+            {
+                Some(text) if !text.trim().is_empty() => text.trim().to_string(),
+                _ => continue,
+            };
+            let value = match kind
+            {
+                Kind::Bool => match text.to_lowercase().as_str()
+                {
+                    "1" | "true" | "yes" | "on" => Value::Bool(true),
+                    "0" | "false" | "no" | "off" => Value::Bool(false),
+                    _ => return Err(WordleError::bad_argument(&name, "EXPECTED TRUE OR FALSE")),
+                },
+                Kind::Number => match text.parse::<u64>()
+                {
+                    Ok(number) => Value::from(number),
+                    Err(_) => return Err(WordleError::bad_argument(&name, "EXPECTED A NUMBER")),
+                },
+                Kind::Text => Value::String(text),
+            };
+            self.values[i] = Some((value, Source::Env(name)));
+        }
+        Ok(())
+    }
+
+    //合并后的配置，某一项的取值类型不对时报告该项及其来源
+    pub fn config(&self) -> Result<Config, WordleError>
+    {
+        let mut map = Map::new();
+        for ((key, _), value) in KEYS.iter().zip(self.values.iter())
+        {
+            if let Some((value, source)) = value
+            {
+                let single = Map::from_iter([(key.to_string(), value.clone())]);
+                if let Err(error) = serde_json::from_value::<Config>(Value::Object(single))
+                {
+                    return Err(WordleError::Config{ path: source.to_string(), reason: format!("{}: {}", key, error) });
+                }
+                map.insert(key.to_string(), value.clone());
+            }
+        }
+        serde_json::from_value(Value::Object(map)).map_err(|error| WordleError::Config{ path: "config".to_string(), reason: error.to_string() })
+    }
+
+    //某一项的取值与来源
+    pub fn get(&self, key: &str) -> Option<&(Value, Source)>
+    {
+        KEYS.iter().position(|(name, _)| *name == key).and_then(|i| self.values[i].as_ref())
+    }
+}
+
+//每项一行，格式与 TOML 配置文件相同，注释中为来源，未设置的项整行注释
+impl fmt::Display for Layers
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        for ((key, _), value) in KEYS.iter().zip(self.values.iter())
+        {
+            match value
+            {
+                Some((value, source)) => writeln!(f, "{} = {}  # {}", key, value, source)?,
+                None => writeln!(f, "# {} (not set)", key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn index(key: &str) -> usize
+{
+    KEYS.iter().position(|(name, _)| *name == key).expect("unknown config key")
+}
+
+//内置默认值，没有默认值的项不设置
+pub fn defaults() -> Config
+{
+    Config
+    {
+        random: Some(false),
+        difficult: Some(false),
+        ultra_hard: Some(false),
+        stats: Some(false),
+        dictionary: Some(DEFAULT_DICTIONARY.to_string()),
+        length: Some(DEFAULT_LENGTH as u64),
+        max_guesses: Some(DEFAULT_MAX_GUESSES as u64),
+        boards: Some(1),
+        absurdle: Some(false),
+        theme: Some("default".to_string()),
+        no_animation: Some(false),
+        ..Config::default()
+    }
+}
+
+//用户配置目录：XDG_CONFIG_HOME/wordle 或 ~/.config/wordle
+pub fn user_dir() -> Option<PathBuf>
+{
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty())
+    {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("wordle"))
+}
+
+//用户配置文件，config.toml 优先于 config.json，都不存在时为 None
+pub fn user_file() -> Option<PathBuf>
+{
+    let dir = user_dir()?;
+    ["config.toml", "config.json"].iter().map(|name| dir.join(name)).find(|path| path.is_file())
+}
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+use crate::error::WordleError;
+use crate::file;
+
+//内置词典的名称，也是未指定 --dictionary 时使用的词典
+pub const DEFAULT_DICTIONARY: &str = "en";
+//内置词典的字母表与键盘布局，也是用户词典未指定时的默认值
+pub const ENGLISH_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const QWERTY: [&str; 3] = [
+    "Q W E R T Y U I O P",
+    " A S D F G H J K L",
+    "  Z X C V B N M"
+];
+//键盘最多的行数，受TUI中键盘区域的高度限制
+pub const MAX_ROWS: usize = 3;
+
+//词典目录中的 dictionary.toml，各项都可以省略
+#[derive(Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct Manifest
+{
+    description: Option<String>,
+    alphabet: Option<String>,
+    keyboard: Option<Vec<String>>,
+}
+
+//一个词典：答案与候选词库、字母表与键盘布局
+//内置的 en 使用编译进程序的词库，此时 final_set 与 acceptable_set 为 None
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary
+{
+    pub name: String,
+    pub description: String,
+    pub alphabet: String,
+    pub keyboard: Vec<String>,
+    pub final_set: Option<String>,
+    pub acceptable_set: Option<String>,
+}
+
+impl Default for Dictionary
+{
+    fn default() -> Dictionary
+    {
+        Dictionary::english()
+    }
+}
+
+impl Dictionary
+{
+    pub fn english() -> Dictionary
+    {
+        Dictionary
+        {
+            name: DEFAULT_DICTIONARY.to_string(),
+            description: "English (builtin)".to_string(),
+            alphabet: ENGLISH_ALPHABET.to_string(),
+            keyboard: QWERTY.iter().map(|row| row.to_string()).collect(),
+            final_set: None,
+            acceptable_set: None,
+        }
+    }
+
+    //按名称打开词典：en 为内置词典，其他名称在 dictionaries_dir() 中查找同名目录
+    pub fn open(name: &str) -> Result<Dictionary, WordleError>
+    {
+        if name == DEFAULT_DICTIONARY
+        {
+            return Ok(Dictionary::english());
+        }
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(WordleError::bad_argument("--dictionary", &format!("INVALID NAME {}", name)));
+        }
+        match dictionaries_dir().map(|dir| dir.join(name)).filter(|dir| dir.is_dir())
+        {
+            Some(dir) => Dictionary::load(name, &dir),
+            None => Err(WordleError::bad_argument("--dictionary", &format!("NO DICTIONARY NAMED {}", name))),
+        }
+    }
+
+    //读取词典目录：final.txt 为答案，acceptable.txt 为候选词（没有时与答案相同），dictionary.toml 可以省略
+    pub fn load(name: &str, dir: &Path) -> Result<Dictionary, WordleError>
+    {
+        let path = dir.join("dictionary.toml");
+        let invalid = |reason: String| WordleError::Config{ path: path.display().to_string(), reason };
+        let manifest = if path.is_file()
+        {
+            let text = fs::read_to_string(&path).map_err(|error| invalid(error.to_string()))?;
+            toml::from_str::<Manifest>(&text).map_err(|error| invalid(file::toml_reason(&text, &error)))?
+        }
+        else
+        {
+            Manifest::default()
+        };
+
+        let alphabet = uppercase(manifest.alphabet.as_deref().unwrap_or(ENGLISH_ALPHABET));
+        check_alphabet(&alphabet).map_err(invalid)?;
+        let keyboard = match manifest.keyboard
+        {
+            Some(rows) => rows.iter().map(|row| uppercase(row)).collect(),
+            None if alphabet == ENGLISH_ALPHABET => QWERTY.iter().map(|row| row.to_string()).collect(),
+            None => default_keyboard(&alphabet),
+        };
+        check_keyboard(&keyboard, &alphabet).map_err(invalid)?;
+
+        let final_set = dir.join("final.txt");
+        if !final_set.is_file()
+        {
+            return Err(WordleError::WordList{ path: final_set.display().to_string(), line: None, reason: "NOT FOUND".to_string() });
+        }
+        let acceptable_set = Some(dir.join("acceptable.txt")).filter(|path| path.is_file()).unwrap_or_else(|| final_set.clone());
+        Ok(Dictionary
+        {
+            name: name.to_string(),
+            description: manifest.description.unwrap_or_default(),
+            alphabet,
+            keyboard,
+            final_set: Some(final_set.display().to_string()),
+            acceptable_set: Some(acceptable_set.display().to_string()),
+        })
+    }
+}
+
+//名称与说明，之后为字母表与键盘布局
+impl fmt::Display for Dictionary
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "NAME {}", self.name)?;
+        if !self.description.is_empty()
+        {
+            writeln!(f, "DESCRIPTION {}", self.description)?;
+        }
+        writeln!(f, "ALPHABET {} ({} LETTERS)", self.alphabet, self.alphabet.chars().count())?;
+        writeln!(f, "FINAL {}", self.final_set.as_deref().unwrap_or("builtin:final"))?;
+        writeln!(f, "ACCEPTABLE {}", self.acceptable_set.as_deref().unwrap_or("builtin:acceptable"))?;
+        write!(f, "KEYBOARD")?;
+        for row in self.keyboard.iter()
+        {
+            write!(f, "\n{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+//字母表不能为空，只能包含字母，每个字母只能出现一次
+fn check_alphabet(alphabet: &str) -> Result<(), String>
+{
+    if alphabet.is_empty()
+    {
+        return Err("EMPTY ALPHABET".to_string());
+    }
+    for (index, letter) in alphabet.chars().enumerate()
+    {
+        if !letter.is_alphabetic()
+        {
+            return Err(format!("INVALID LETTER '{}' IN ALPHABET", letter));
+        }
+        if alphabet.chars().take(index).any(|other| other == letter)
+        {
+            return Err(format!("LETTER {} REPEATED IN ALPHABET", letter));
+        }
+    }
+    Ok(())
+}
+
+//键盘为 1 到 MAX_ROWS 行，空格用于对齐，字母表中的每个字母恰好出现一次
+fn check_keyboard(keyboard: &[String], alphabet: &str) -> Result<(), String>
+{
+    if keyboard.is_empty() || keyboard.len() > MAX_ROWS
+    {
+        return Err(format!("KEYBOARD MUST HAVE 1 TO {} ROWS", MAX_ROWS));
+    }
+    let keys: Vec<char> = keyboard.iter().flat_map(|row| row.chars()).filter(|key| *key != ' ').collect();
+    if let Some(key) = keys.iter().find(|key| !alphabet.contains(**key))
+    {
+        return Err(format!("KEY {} IS NOT IN THE ALPHABET", key));
+    }
+    for letter in alphabet.chars()
+    {
+        match keys.iter().filter(|key| **key == letter).count()
+        {
+            0 => return Err(format!("LETTER {} IS MISSING FROM THE KEYBOARD", letter)),
+            1 => {}
+            _ => return Err(format!("LETTER {} REPEATED ON THE KEYBOARD", letter)),
+        }
+    }
+    Ok(())
+}
+
+//未指定键盘时按字母表顺序平均分为 MAX_ROWS 行
+fn default_keyboard(alphabet: &str) -> Vec<String>
+{
+    let letters: Vec<String> = alphabet.chars().map(|letter| letter.to_string()).collect();
+    letters
+        .chunks(letters.len().div_ceil(MAX_ROWS))
+        .map(|row| row.join(" "))
+        .collect()
+}
+
+//单个字母的大写形式，没有单个字符的大写形式时（如 ß）保持不变
+pub fn upper(letter: char) -> char
+{
+    let mut upper = letter.to_uppercase();
+    match (upper.next(), upper.next())
+    {
+        (Some(upper), None) => upper,
+        _ => letter,
+    }
+}
+
+//逐个字母转为大写，单词长度不变
+pub fn uppercase(word: &str) -> String
+{
+    word.chars().map(upper).collect()
+}
+
+//是否为 alphabet 中的字母，比较前先转为大写
+pub fn is_letter(alphabet: &str, letter: char) -> bool
+{
+    alphabet.contains(upper(letter))
+}
+
+//数据目录：WORDLE_DATA_DIR，否则为 XDG_DATA_HOME/wordle 或 ~/.local/share/wordle
+pub fn data_dir() -> Option<PathBuf>
+{
+    if let Some(dir) = std::env::var_os("WORDLE_DATA_DIR").filter(|dir| !dir.is_empty())
+    {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_DATA_HOME").filter(|dir| !dir.is_empty())
+    {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share").join("wordle"))
+}
+
+//用户词典所在的目录，每个词典一个子目录，目录名即词典名
+pub fn dictionaries_dir() -> Option<PathBuf>
+{
+    data_dir().map(|dir| dir.join("dictionaries"))
+}
+
+//全部词典的名称：内置的 en 在前，之后为用户词典，按名称排序
+pub fn names() -> Vec<String>
+{
+    let mut names: Vec<String> = dictionaries_dir()
+        .and_then(|dir| fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != DEFAULT_DICTIONARY)
+        .collect();
+    names.sort();
+    names.insert(0, DEFAULT_DICTIONARY.to_string());
+    names
+}
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::io;
+
+//程序中的错误，每一类对应一个退出码，测试模式下的输出不受影响
+#[derive(Debug)]
+pub enum WordleError
+{
+    //不合法的参数或参数组合，flag 为出错的参数，如 --day 或 WORDLE_SEED
+    BadArgument{ flag: String, reason: String },
+    //配置文件读取失败或内容不合法
+    Config{ path: String, reason: String },
+    //词库读取失败或内容不合法，line 为出错的行号，从 1 开始
+    WordList{ path: String, line: Option<usize>, reason: String },
+    //存档读写失败或内容不合法，position 为 JSON 中出错的 (行, 列)
+    State{ path: String, position: Option<(usize, usize)>, reason: String },
+    //不合法的猜测或答案，reason 为具体原因，如 3RD LETTER MUST BE A
+    InvalidGuess{ guess: String, reason: String },
+    //不合法的 Y / N 回答
+    InvalidInput(String),
+    //终端或标准输入输出的错误
+    Io(io::Error),
+}
+
+impl WordleError
+{
+    pub fn bad_argument(flag: &str, reason: &str) -> WordleError
+    {
+        WordleError::BadArgument{ flag: flag.to_string(), reason: reason.to_string() }
+    }
+
+    pub fn invalid_guess(guess: &str, reason: &str) -> WordleError
+    {
+        WordleError::InvalidGuess{ guess: guess.to_string(), reason: reason.to_string() }
+    }
+
+    //进程的退出码，2 与 clap 的参数错误相同
+    pub fn exit_code(&self) -> u8
+    {
+        match self
+        {
+            WordleError::Io(_) => 1,
+            WordleError::BadArgument{ .. } => 2,
+            WordleError::Config{ .. } => 3,
+            WordleError::WordList{ .. } => 4,
+            WordleError::State{ .. } => 5,
+            WordleError::InvalidGuess{ .. } | WordleError::InvalidInput(_) => 6,
+        }
+    }
+}
+
+//猜测不合法时只显示原因，便于在游戏中提示
+impl fmt::Display for WordleError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            WordleError::BadArgument{ flag, reason } => write!(f, "INVALID ARGUMENT {}: {}", flag, reason),
+            WordleError::Config{ path, reason } => write!(f, "INVALID CONFIG {}: {}", path, reason),
+            WordleError::WordList{ path, line: Some(line), reason } => write!(f, "INVALID WORD LIST {} LINE {}: {}", path, line, reason),
+            WordleError::WordList{ path, line: None, reason } => write!(f, "INVALID WORD LIST {}: {}", path, reason),
+            WordleError::State{ path, position: Some((line, column)), reason } =>
+            {
+                write!(f, "INVALID STATE {} LINE {} COLUMN {}: {}", path, line, column, reason)
+            }
+            WordleError::State{ path, position: None, reason } => write!(f, "INVALID STATE {}: {}", path, reason),
+            WordleError::InvalidGuess{ reason, .. } => write!(f, "{}", reason),
+            WordleError::InvalidInput(reason) => write!(f, "{}", reason),
+            WordleError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for WordleError
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self
+        {
+            WordleError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for WordleError
+{
+    fn from(error: io::Error) -> WordleError
+    {
+        WordleError::Io(error)
+    }
+}
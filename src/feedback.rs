@@ -0,0 +1,187 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+//默认单词长度与支持的最大长度，最大长度受结果编码的 u16 限制
+pub const DEFAULT_LENGTH: usize = 5;
+pub const MAX_LENGTH: usize = 8;
+
+//单个字母的比较结果，按 红 < 黄 < 绿 排序，便于取最好结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LetterFeedback
+{
+    Absent,
+    Present,
+    Correct,
+}
+
+impl LetterFeedback
+{
+    //对应的字母：R / Y / G
+    pub fn to_char(self) -> char
+    {
+        match self
+        {
+            LetterFeedback::Absent => 'R',
+            LetterFeedback::Present => 'Y',
+            LetterFeedback::Correct => 'G',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<LetterFeedback>
+    {
+        match c.to_ascii_uppercase()
+        {
+            'R' => Some(LetterFeedback::Absent),
+            'Y' => Some(LetterFeedback::Present),
+            'G' => Some(LetterFeedback::Correct),
+            _ => None,
+        }
+    }
+
+    fn digit(self) -> u8
+    {
+        self as u8
+    }
+
+    fn from_digit(digit: u8) -> LetterFeedback
+    {
+        match digit
+        {
+            0 => LetterFeedback::Absent,
+            1 => LetterFeedback::Present,
+            _ => LetterFeedback::Correct,
+        }
+    }
+}
+
+//一次猜测的全部结果，以三进制压缩为一个 u16，第 i 位对应第 i 个字母
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pattern
+{
+    code: u16,
+    length: u8,
+}
+
+impl Pattern
+{
+    pub fn new(letters: &[LetterFeedback]) -> Pattern
+    {
+        assert!(letters.len() <= MAX_LENGTH, "word longer than MAX_LENGTH");
+        let mut code: u16 = 0;
+        for letter in letters.iter().rev()
+        {
+            code = code * 3 + letter.digit() as u16;
+        }
+        Pattern
+        {
+            code,
+            length: letters.len() as u8,
+        }
+    }
+
+    //全部为绿色的结果
+    pub fn correct(length: usize) -> Pattern
+    {
+        Pattern
+        {
+            code: (Pattern::count(length) - 1) as u16,
+            length: length as u8,
+        }
+    }
+
+    //该长度下不同结果的总数
+    pub fn count(length: usize) -> usize
+    {
+        3usize.pow(length as u32)
+    }
+
+    pub fn from_code(code: u16, length: usize) -> Option<Pattern>
+    {
+        if length <= MAX_LENGTH && (code as usize) < Pattern::count(length)
+        {
+            Some(Pattern{ code, length: length as u8 })
+        }
+        else
+        {
+            None
+        }
+    }
+
+    pub fn code(self) -> u16
+    {
+        self.code
+    }
+
+    pub fn length(self) -> usize
+    {
+        self.length as usize
+    }
+
+    //第 index 个字母的结果
+    pub fn get(self, index: usize) -> LetterFeedback
+    {
+        LetterFeedback::from_digit((self.code / 3u16.pow(index as u32) % 3) as u8)
+    }
+
+    pub fn letters(self) -> impl Iterator<Item = LetterFeedback>
+    {
+        (0..self.length()).map(move |index| self.get(index))
+    }
+
+    pub fn is_correct(self) -> bool
+    {
+        self == Pattern::correct(self.length())
+    }
+}
+
+//以 "GYRRG" 的形式输出
+impl fmt::Display for Pattern
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        for letter in self.letters()
+        {
+            write!(f, "{}", letter.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+//从 "GYRRG" 的形式解析，大小写均可
+impl FromStr for Pattern
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Pattern, String>
+    {
+        let s = s.trim();
+        if s.is_empty() || s.chars().count() > MAX_LENGTH
+        {
+            return Err("INVALID RESULT".to_string());
+        }
+        let letters = s
+            .chars()
+            .map(|c| LetterFeedback::from_char(c).ok_or("INVALID RESULT".to_string()))
+            .collect::<Result<Vec<LetterFeedback>, String>>()?;
+        Ok(Pattern::new(&letters))
+    }
+}
+
+//存档中同样使用字符串形式
+impl Serialize for Pattern
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Pattern, D::Error>
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -1,26 +1,111 @@
 use serde::{Serialize, Deserialize};
 use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter};
-use std::collections::HashSet;
+use std::io::{BufReader, BufWriter};
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::io::Read;
+use crate::error::WordleError;
 
 //创建具有Serialize和Deserialize的结构体记录数据，方便读写
+//多棋盘的一局在 answers 中记录全部答案，answer 为第一个棋盘的答案
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Round
 {
     pub answer: String,
     pub guesses: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answers: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Round
+{
+    //该局的全部答案
+    pub fn all_answers(&self) -> &[String]
+    {
+        if self.answers.is_empty() { std::slice::from_ref(&self.answer) } else { &self.answers }
+    }
+
+    //所有答案都被猜中即为成功
+    pub fn is_success(&self) -> bool
+    {
+        if self.answers.is_empty()
+        {
+            self.guesses.last() == Some(&self.answer)
+        }
+        else
+        {
+            self.answers.iter().all(|answer| self.guesses.contains(answer))
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct GameData
 {
     pub total_rounds: i32,
     pub games: Vec<Round>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+//由存档计算出的统计数据
+#[derive(Debug, Clone)]
+pub struct Stats
+{
+    pub success: i32,
+    pub failed: i32,
+    pub average: f32,
+    pub words: Vec<(String, i32)>,
+}
+
+impl GameData
+{
+    //记录正在进行的一局，第一次猜测时新增，之后覆盖最后一局
+    pub fn update(&mut self, round: Round)
+    {
+        if round.guesses.len() > 1
+        {
+            self.games.pop();
+        }
+        else
+        {
+            self.total_rounds += 1;
+        }
+        self.games.push(round);
+    }
+
+    //成功局数、失败局数、平均次数与按使用次数排序的单词
+    pub fn stats(&self) -> Stats
+    {
+        let mut success: i32 = 0;
+        let mut success_try: i32 = 0;
+        let mut total_word: HashMap<String, i32> = HashMap::new();
+        for round in self.games.iter()
+        {
+            if round.is_success()
+            {
+                success += 1;
+                success_try += round.guesses.len() as i32;
+            }
+            for word in round.guesses.iter()
+            {
+                *total_word.entry(word.to_string()).or_insert(0) += 1;
+            }
+        }
+        let average = if success != 0 { (success_try as f32) / (success as f32) } else { 0.0 };
+        let mut words: Vec<(String, i32)> = total_word.into_iter().collect();
+        words.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Stats
+        {
+            success,
+            failed: self.total_rounds - success,
+            average,
+            words,
+        }
+    }
+}
+
+//配置文件中的各项，未知的键视为错误
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
 pub struct Config
 {
     pub random: Option<bool>,
@@ -28,58 +113,53 @@ pub struct Config
     pub stats: Option<bool>,
     pub day: Option<u64>,
     pub seed: Option<u64>,
+    pub dictionary: Option<String>,
     pub final_set: Option<String>,
     pub acceptable_set: Option<String>,
     pub state: Option<String>,
     pub word: Option<String>,
+    pub length: Option<u64>,
+    pub max_guesses: Option<u64>,
+    pub boards: Option<u64>,
+    pub absurdle: Option<bool>,
+    pub ultra_hard: Option<bool>,
+    pub no_animation: Option<bool>,
+    pub theme: Option<String>,
+    pub share: Option<String>,
 }
 
-//读取词库
-pub fn read_txt(file_name: &str) -> std::io::Result<Vec<String>>
+//读取存档，JSON 格式错误时记录出错的行与列
+pub fn read_state(file_name: &str) -> Result<GameData, WordleError>
 {
-    let mut set: HashSet<String> = HashSet::new();
-    let mut words: Vec<String> = Vec::new();
-
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
-    for line in reader.lines()
+    let invalid = |position: Option<(usize, usize)>, reason: String| WordleError::State
     {
-        let word = line?.to_uppercase();
-        if set.insert(word.clone())
-        {
-            words.push(word);
-        }
-    }
-    words.sort();
-    Ok(words)
-}
-
-//读取存档
-pub fn read_state(file_name: &str) -> std::io::Result<GameData>
-{
+        path: file_name.to_string(),
+        position,
+        reason,
+    };
     match File::open(file_name)
     {
         Ok(file) =>
         {
             let mut reader = BufReader::new(file);
             let mut content = String::new();
-            reader.read_to_string(&mut content)?;
+            reader.read_to_string(&mut content).map_err(|error| invalid(None, error.to_string()))?;
             if content.trim() == "{}"//json为"{}"
             {
-                Ok(GameData{ total_rounds: 0, games: Vec::new(), })
+                Ok(GameData::default())
             }
             else
             {
                 match serde_json::from_str(&content)
                 {
                     Ok(game_data) => Ok(game_data),
-                    Err(error) if error.is_eof() => Ok(GameData{ total_rounds: 0, games: Vec::new(), }),//json为空
-                    Err(error) => Err(error.into()),//其他错误
+                    Err(error) if error.is_eof() => Ok(GameData::default()),//json为空
+                    Err(error) => Err(invalid(Some((error.line(), error.column())), strip_position(&error))),//其他错误
                 }
             }
         }
-        Err(error) if error.kind() == ErrorKind::NotFound => Ok(GameData{ total_rounds: 0, games: Vec::new(), }),//json不存在
-        Err(error) => Err(error),//其他错误
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(GameData::default()),//json不存在
+        Err(error) => Err(invalid(None, error.to_string())),//其他错误
     }
 }
 
@@ -92,11 +172,38 @@ pub fn write_state(game_data: &GameData, file_name: &str) -> std::io::Result<()>
     Ok(())
 }
 
-//读取配置
-pub fn read_config(file_name: &str) -> std::io::Result<Config>
+//读取配置，扩展名为 .toml 时按 TOML 解析，否则按 JSON 解析
+pub fn read_config(file_name: &str) -> Result<Config, WordleError>
 {
-    let file = File::open(file_name)?;
-    let reader = BufReader::new(file);
-    let config = serde_json::from_reader(reader)?;
-    Ok(config)
+    let invalid = |reason: String| WordleError::Config{ path: file_name.to_string(), reason };
+    let text = std::fs::read_to_string(file_name).map_err(|error| invalid(error.to_string()))?;
+    if file_name.ends_with(".toml")
+    {
+        toml::from_str(&text).map_err(|error| invalid(toml_reason(&text, &error)))
+    }
+    else
+    {
+        serde_json::from_str(&text).map_err(|error| invalid(error.to_string()))
+    }
+}
+
+//TOML 的错误信息与所在的行号
+pub(crate) fn toml_reason(text: &str, error: &toml::de::Error) -> String
+{
+    match error.span()
+    {
+        Some(span) => format!("{} at line {}", error.message(), text[..span.start].lines().count().max(1)),
+        None => error.message().to_string(),
+    }
+}
+
+//serde_json 的错误信息末尾带有位置，位置已单独记录
+fn strip_position(error: &serde_json::Error) -> String
+{
+    let message = error.to_string();
+    match message.rfind(" at line ")
+    {
+        Some(index) => message[..index].to_string(),
+        None => message,
+    }
 }
\ No newline at end of file
@@ -1,20 +1,17 @@
 use crate::builtin_words::{ACCEPTABLE, FINAL};
-use std::{cmp::Ordering, collections::HashMap};
+use crate::dictionary::{upper, uppercase};
+use crate::error::WordleError;
+use crate::feedback::{LetterFeedback, Pattern, MAX_LENGTH};
+use crate::session::{GameSession, MultiSession};
+use crate::theme::Theme;
+use std::cmp::Ordering;
 use rand::prelude::*;
 use rand::rngs::StdRng;
-use crossterm::{execute, style::{Color, Print, ResetColor, SetForegroundColor},};
+use crossterm::{execute, style::{Attribute, Print, ResetColor, SetAttribute, SetForegroundColor},};
 use std::io::stdout;
-use crossterm::event::{self, Event, KeyCode};
-use std::io;
-use tui::backend::Backend;
-use tui::layout::{Constraint, Direction, Layout};
-use tui::style::Style;
-use tui::text::{Span, Spans};
-use tui::widgets::{Block, Borders, Paragraph};
-use tui::Terminal;
 
 //判断猜测是否合法
-pub fn guess_is_valid(guess: &str, guess_file: &Vec<String>) -> Result<String, String>
+pub fn guess_is_valid(guess: &str, guess_file: &[String]) -> Result<String, WordleError>
 {
     if !guess_file.is_empty()
     {
@@ -25,18 +22,17 @@ pub fn guess_is_valid(guess: &str, guess_file: &Vec<String>) -> Result<String, S
                 return Ok(guess.to_string())
             }
         }
-        return Err("INVALID".to_string());
-
+        return Err(WordleError::invalid_guess(guess, "INVALID"));
     }
     for word in ACCEPTABLE
     {
         if guess == word.to_uppercase() {return Ok(guess.to_string());}
     }
-    Err("INVALID".to_string())
+    Err(WordleError::invalid_guess(guess, "INVALID"))
 }
 
 //判断答案是否合法
-pub fn answer_is_valid(answer: &str, answer_file: &Vec<String>) -> Result<String, String>
+pub fn answer_is_valid(answer: &str, answer_file: &[String]) -> Result<String, WordleError>
 {
     if !answer_file.is_empty()
     {
@@ -47,551 +43,386 @@ pub fn answer_is_valid(answer: &str, answer_file: &Vec<String>) -> Result<String
                 return Ok(answer.to_string())
             }
         }
-        return Err("INVALID".to_string());
-
+        return Err(WordleError::invalid_guess(answer, "INVALID"));
     }
     for word in FINAL
     {
         if answer == word.to_uppercase() {return Ok(answer.to_string());}
     }
-    Err("INVALID".to_string())
+    Err(WordleError::invalid_guess(answer, "INVALID"))
 }
 
-//代表单词所有信息的单词结构体
+//不足 MAX_LENGTH 的位置的占位字符
+const NOT_LETTER: char = '\0';
+//英文字母表的大小
+const ALPHABET: usize = 26;
+
+//A 到 Z 转换为 0..26 的编号，其他字符为 None
+fn ascii_index(letter: char) -> Option<usize>
+{
+    let index = (letter as u32).wrapping_sub('A' as u32) as usize;
+    (index < ALPHABET).then_some(index)
+}
+
+//代表单词所有信息的单词结构体，比较时不需要堆分配
+//字母按字符保存，任意字母表的单词都可以比较
+//全部为 A 到 Z 的单词另存各字母的数量，比较时不必逐个查找剩下的字母
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Word
 {
     pub origin: String,
-    letter: HashMap<char, Vec<u8>>,
+    length: usize,
+    letters: [char; MAX_LENGTH],
+    counts: Option<[u8; ALPHABET]>,
 }
 
 impl Word
 {
     pub fn new(word: &str) -> Word
     {
-        let mut tmp_word = Word
+        let length = word.chars().count().min(MAX_LENGTH);
+        let letters = Word::letters_of(word);
+        let mut counts = Some([0u8; ALPHABET]);
+        for &letter in letters[..length].iter()
         {
-            origin: word.to_string(),
-            letter: HashMap::new(),
-        };
-        let mut count: u8 = 0;
-        for tmp_letter in word.chars()
+            match (ascii_index(letter), counts.as_mut())
+            {
+                (Some(index), Some(counts)) => counts[index] += 1,
+                _ => counts = None,
+            }
+        }
+        Word
         {
-            let tmp = tmp_word.letter.entry(tmp_letter).or_insert(Vec::new());
-            tmp.push(count);
-            count += 1;
+            origin: word.to_string(),
+            length,
+            letters,
+            counts,
         }
-        tmp_word
     }
 
-    //比较两个单词间的差异
-    pub fn compare(&self, guess: &str)-> [u8; 5]
+    //单词长度，也是比较结果的长度
+    pub fn length(&self) -> usize
+    {
+        self.length
+    }
+
+    //单词的大写字母，不足的位置用占位字符补齐
+    fn letters_of(word: &str) -> [char; MAX_LENGTH]
     {
-        let mut tmp_result: [u8; 5] = [0; 5];
-        let mut answer_map = self.letter.clone();
-        let mut count: usize = 0;
-        for i in guess.chars()
+        let mut letters = [NOT_LETTER; MAX_LENGTH];
+        for (letter, char) in letters.iter_mut().zip(word.chars())
         {
-            let tmp_answer_vec = answer_map.get_mut(&i);
-            match tmp_answer_vec
-            {
-                Some(answer_vec) => 
-                {
-                    tmp_result[count] = 2;
-                    let t = answer_vec.clone();
-                    for (j, k ) in t.iter().enumerate()
-                    {
-                        if *k as usize == count
-                        {
-                            answer_vec.remove(j);
-                            tmp_result[count] = 3;
-                        }
-                    }
-                },
-                None =>
-                {
-                    tmp_result[count] = 1;
-                },
-            }
-            count += 1;
+            *letter = upper(char);
         }
-        count = 0;
-        for j in guess.chars()
-        {
-            if tmp_result[count] == 1 || tmp_result[count] == 3
-            {
-                count += 1;
-                continue;
-            }
-            let answer_vec = answer_map.get_mut(&j).unwrap();
-            if answer_vec.is_empty()
-            {
-                tmp_result[count] = 1;
-                count += 1;
-                continue;
-            }
-            answer_vec.pop();
-            tmp_result[count] = 2;
-            count += 1;
-        }
-        tmp_result
+        letters
     }
-}
 
-//生成答案单词
-pub fn gen_answer(word: &str, answer_file: &Vec<String>) -> Result<Word, String>
-{
-    let answer = answer_is_valid(&word.to_uppercase(), answer_file)?;
-    Ok(Word::new(&answer))
-}
+    //比较两个单词间的差异
+    pub fn compare(&self, guess: &str) -> Pattern
+    {
+        self.compare_letters(&Word::letters_of(guess))
+    }
 
-//生成猜测单词
-pub fn gen_guess(word: &str, guess_file: &Vec<String>) -> Result<Word, String>
-{
-    let guess = guess_is_valid(&word.to_uppercase(), guess_file)?;
-    Ok(Word::new(&guess))
-}
+    //与另一个已经构造好的单词比较
+    pub fn compare_word(&self, guess: &Word) -> Pattern
+    {
+        self.compare_letters(&guess.letters)
+    }
 
-//从标准输入获取答案
-pub fn std_answer(answer_file: &Vec<String>) -> Word
-{
-    let mut word: String = "".to_string();
-    loop
+    //先标出位置正确的字母，再从左到右在答案剩下的字母中找出黄色
+    fn compare_letters(&self, guess: &[char; MAX_LENGTH]) -> Pattern
     {
-        match std::io::stdin().read_line(&mut word)
+        match self.counts
         {
-            Ok(_) => (),
-            Err(error) => println!("ERROR: {}", error),
-        }
-        match gen_answer(word.trim(), answer_file)
-        {
-            Ok(tmp) => break tmp,
-            Err(warning) => 
-            {
-                println!("{}", warning);
-            }
+            Some(counts) => self.compare_ascii(guess, counts),
+            None => self.compare_chars(guess),
         }
-        word = "".to_string();
     }
-}
 
-//从TUI获取答案
-pub fn tui_answer<B: Backend>(terminal: &mut Terminal<B>, answer_file: &Vec<String>) -> Word
-{
-    let mut word: String = "".to_string();
-    let mut output: String = String::new();
-    output += "CHOOSE AN ANSWER\n";
-    loop
+    //答案只有 A 到 Z 时按剩余数量标出黄色，猜测中的其他字符都不在答案中
+    fn compare_ascii(&self, guess: &[char; MAX_LENGTH], mut counts: [u8; ALPHABET]) -> Pattern
     {
-        match get_input_str(terminal, &output)
+        let guess = &guess[..self.length];
+        let mut tmp_result = [LetterFeedback::Absent; MAX_LENGTH];
+        for (count, &letter) in guess.iter().enumerate()
         {
-            Ok(tmp) => word = tmp,
-            Err(_) => 
+            if letter == self.letters[count]
             {
-                word = "".to_string();
-                continue;
+                if let Some(index) = ascii_index(letter)
+                {
+                    tmp_result[count] = LetterFeedback::Correct;
+                    counts[index] -= 1;
+                }
             }
         }
-        match gen_answer(word.trim(), answer_file)
+        for (count, &letter) in guess.iter().enumerate()
         {
-            Ok(tmp) => break tmp,
-            Err(warning) => 
+            if tmp_result[count] == LetterFeedback::Correct
             {
-                output = output + &warning + " ANSWER\n";
                 continue;
             }
+            if let Some(index) = ascii_index(letter).filter(|&index| counts[index] > 0)
+            {
+                tmp_result[count] = LetterFeedback::Present;
+                counts[index] -= 1;
+            }
         }
+        Pattern::new(&tmp_result[..self.length])
     }
-}
 
-//从标准输入获取猜测
-pub fn std_guess(guess_file: &Vec<String>, tmp_result: &[u8; 5], record: &Vec<String>, difficult: &bool) -> Word
-{
-    let mut word: String = "".to_string();
-    loop
+    //任意字母表：在答案剩下的字母中逐个查找
+    fn compare_chars(&self, guess: &[char; MAX_LENGTH]) -> Pattern
     {
-        match std::io::stdin().read_line(&mut word)
+        let guess = &guess[..self.length];
+        let mut tmp_result = [LetterFeedback::Absent; MAX_LENGTH];
+        let mut left = [NOT_LETTER; MAX_LENGTH];
+        for (count, &letter) in guess.iter().enumerate()
         {
-            Ok(_) => (),
-            Err(error) =>
+            if letter != NOT_LETTER && letter == self.letters[count]
             {
-                println!("ERROR: {}", error);
-                word = "".to_string();
-                continue;
+                tmp_result[count] = LetterFeedback::Correct;
             }
-        }
-        word = word.to_uppercase().to_string();
-        if *difficult && !record.is_empty()
-        {
-            let last: String = record.last().unwrap().to_string();
-            let mut is_valid: bool = true;
-            let mut count: usize = 0;
-            for i in last.chars()
+            else
             {
-                match tmp_result[count]
-                {
-                    3 =>
-                    {
-                        if word.chars().nth(count).unwrap() != i
-                        {
-                            is_valid = false;
-                            break;
-                        }
-                    }
-                    2 =>
-                    {
-                        match word.find(i)
-                        {
-                            Some(_) => (),
-                            None =>
-                            {
-                                is_valid = false;
-                                break;
-                            }
-                        }
-                    }
-                    1 | 0 => (),
-                    _ =>
-                    {
-                        is_valid = false;
-                        break;
-                    }
-                }
-                count += 1;
+                left[count] = self.letters[count];
             }
-            if !is_valid
+        }
+        for (count, &letter) in guess.iter().enumerate()
+        {
+            if letter == NOT_LETTER || tmp_result[count] == LetterFeedback::Correct
             {
-                println!("INVALID");
-                word = "".to_string();
                 continue;
             }
-        }
-        match gen_guess(word.trim(), guess_file)
-        {
-            Ok(tmp) => break tmp,
-            Err(warning) => 
+            if let Some(found) = left[..self.length].iter_mut().find(|left| **left == letter)
             {
-                println!("{}", warning);
+                tmp_result[count] = LetterFeedback::Present;
+                *found = NOT_LETTER;
             }
         }
-        word = "".to_string();
+        Pattern::new(&tmp_result[..self.length])
     }
 }
 
-//从TUI获取猜测
-pub fn tui_guess<B: Backend>(terminal: &mut Terminal<B>, guess_file: &Vec<String>, tmp_result: &[u8; 5], record: &Vec<String>, difficult: &bool, output: &mut Vec<Spans>) -> Word
+//生成答案单词
+pub fn gen_answer(word: &str, answer_file: &[String]) -> Result<Word, WordleError>
+{
+    let answer = answer_is_valid(&uppercase(word), answer_file)?;
+    Ok(Word::new(&answer))
+}
+
+//生成猜测单词
+pub fn gen_guess(word: &str, guess_file: &[String]) -> Result<Word, WordleError>
 {
-    let mut word: String = "".to_string();
-    let mut str: String = String::new();
-    loop
+    let guess = guess_is_valid(&uppercase(word), guess_file)?;
+    Ok(Word::new(&guess))
+}
+
+//测试模式按顺序输出本次猜测所作用的棋盘的结果与字母状态，已结束的棋盘不再输出
+pub fn test_show_boards(session: &MultiSession, alphabet: &str)
+{
+    for board in session.boards().iter().filter(|board| board.guesses().len() == session.guesses().len())
     {
-        if word.is_empty()
-        {
-            match get_input_span(terminal, &output)
-            {
-                Ok(tmp) => word = tmp,
-                Err(error) =>
-                {
-                    get_input_str(terminal, &error.to_string());
-                    word = "".to_string();
-                    continue;
-                }
-            }
-        }
-        word = word.to_uppercase().to_string();
-        if *difficult && !record.is_empty()
-        {
-            let last: String = record.last().unwrap().to_string();
-            let mut is_valid: bool = true;
-            let mut count: usize = 0;
-            for i in last.chars()
-            {
-                match tmp_result[count]
-                {
-                    3 =>
-                    {
-                        if word.chars().nth(count).unwrap() != i
-                        {
-                            is_valid = false;
-                            break;
-                        }
-                    }
-                    2 =>
-                    {
-                        match word.find(i)
-                        {
-                            Some(_) => (),
-                            None =>
-                            {
-                                is_valid = false;
-                                break;
-                            }
-                        }
-                    }
-                    1 | 0 => (),
-                    _ =>
-                    {
-                        is_valid = false;
-                        break;
-                    }
-                }
-                count += 1;
-            }
-            if !is_valid
-            {
-                str += "INVALID GUESS\n";
-                word = get_input_str(terminal, &str).unwrap();
-                continue;
-            }
-        }
-        match gen_guess(word.trim(), guess_file)
-        {
-            Ok(tmp) => break tmp,
-            Err(warning) => 
-            {
-                str = str + &warning + " GUESS\n";
-                word = get_input_str(terminal, &str).unwrap();
-            }
-        }
+        test_show(board, alphabet);
     }
 }
 
-//测试模式更新结果并输出
-pub fn test_update_and_show(guess: &str, tmp_result: &[u8; 5], mut result: HashMap<char, u8>) -> (HashMap<char, u8>, bool)
+//测试模式输出最近一次猜测的结果与字母状态，字母按字母表的顺序排列
+pub fn test_show(session: &GameSession, alphabet: &str)
 {
-    let mut is_correct: bool = true;
-    let mut count: usize = 0;
     let mut show: String = String::new();
-    for i in guess.chars()
+    if let Some(result) = session.results().last()
     {
-        let tmp = result.entry(i).or_insert(0);
-        if tmp_result[count] > *tmp
-        {
-            *tmp = tmp_result[count];
-        }
-        match tmp_result[count]
-        {
-            3 => {show += "G";},
-            2 =>
-            {
-                show += "Y";
-                is_correct = false;
-            },
-            1 =>
-            {
-                show += "R";
-                is_correct = false;
-            },
-            _ =>
-            {
-                show += "X";
-                is_correct = false;
-            }
-        }
-        count += 1;
+        show += &result.to_string();
     }
     show += " ";
-    for i in "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars()
+    for i in alphabet.chars()
     {
-        let tmp = result.entry(i).or_insert(0);
-        match tmp
-        {
-            3 => {show += "G"},
-            2 => {show += "Y"},
-            1 => {show += "R"},
-            _ => {show += "X"}
-        }
+        show.push(session.keyboard().get(&i).map_or('X', |color| color.to_char()));
     }
     println!("{}", show);
-    (result, is_correct)
 }
 
-//用户模式更新结果并输出
-pub fn user_update_and_show(guesses: &Vec<String>, full_result: &Vec<[u8; 5]>, mut best_result: HashMap<char, u8>) -> (HashMap<char, u8>, bool)
+//用户模式输出全部猜测与键盘，keyboard 为词典的键盘布局
+pub fn user_show(session: &GameSession, theme: Theme, keyboard: &[String])
 {
-    let mut is_correct: bool = true;
-    let mut big_count: usize = 0;
-    let mut color = Color::Black;
-    let mut stdout = stdout();
-    for guess in guesses.iter()
+    for (guess, result) in session.guesses().iter().zip(session.results().iter())
     {
-    print!("     ");
-    let mut small_count: usize = 0;
-    for letter in guess.chars()
-    {
-        let tmp = best_result.entry(letter).or_insert(0);
-        if full_result[big_count][small_count] > *tmp
+        print!("     ");
+        for (letter, color) in guess.chars().zip(result.letters())
         {
-            *tmp = full_result[big_count][small_count];
+            user_print(theme, &theme.mark(letter, Some(color)), Some(color));
+            print!(" ");
         }
-        match full_result[big_count][small_count]
-        {
-            3 => {color = Color::Green;},
-            2 =>
-            {
-                color = Color::Yellow;
-                if big_count == guesses.len() - 1 { is_correct = false; }
-            },
-            1 =>
-            {
-                color = Color::Red;
-                if big_count == guesses.len() - 1 { is_correct = false; }
-            },
-            _ =>
-            {
-                color = Color::Black;
-                if big_count == guesses.len() - 1 { is_correct = false; }
-            }
-        }
-        execute!(stdout, SetForegroundColor(color), Print(letter), ResetColor, Print(" "));
-        small_count += 1;
-    }
-    big_count += 1;
-    println!("");
+        println!();
     }
-    
-    let keyboard = [
-        "Q W E R T Y U I O P",
-        " A S D F G H J K L",
-        "  Z X C V B N M"
-    ];
 
     for row in keyboard.iter()
     {
         for letter in row.chars()
         {
-            match best_result.get(&letter)
+            let color = session.keyboard().get(&letter).copied();
+            match letter
             {
-                Some(3) => color = Color::Green,
-                Some(2) => color = Color::Yellow,
-                Some(1) => color = Color::Red,
-                Some(_) | None => color = Color::Black,
+                ' ' => print!(" "),
+                _ => user_print(theme, &theme.key_mark(letter, color), color),
             }
-            execute!(stdout, SetForegroundColor(color), Print(letter), ResetColor);
         }
-        println!("");
+        println!();
     }
-    (best_result, is_correct)
 }
 
-//TUI模式更新结果并输出
-pub fn tui_update_and_show(guesses: &Vec<String>, full_result: &Vec<[u8; 5]>, mut best_result: HashMap<char, u8>, output: &mut Vec<Spans>) -> (HashMap<char, u8>, bool)
+//每行最多并排显示的棋盘数
+pub(crate) const BOARDS_PER_ROW: usize = 4;
+
+//单个棋盘的显示内容，先是 rows 行猜测，再是键盘，每一段文字附带颜色
+fn board_lines(session: &GameSession, rows: usize, theme: Theme, keyboard: &[String]) -> Vec<Vec<(String, Option<LetterFeedback>)>>
 {
-    let mut is_correct: bool = true;
-    let mut big_count: usize = 0;
-    let mut color = tui::style::Color::Black;
-    for guess in guesses.iter()
-    {
-        let mut spans: Vec<Span> = Vec::new();
-        spans.push(Span::raw("     "));
-    let mut small_count: usize = 0;
-    for letter in guess.chars()
+    let mut lines: Vec<Vec<(String, Option<LetterFeedback>)>> = Vec::new();
+    for row in 0..rows
     {
-        let tmp = best_result.entry(letter).or_insert(0);
-        if full_result[big_count][small_count] > *tmp
-        {
-            *tmp = full_result[big_count][small_count];
-        }
-        match full_result[big_count][small_count]
+        let mut line = Vec::new();
+        match (session.guesses().get(row), session.results().get(row))
         {
-            3 => {color = tui::style::Color::Green;},
-            2 =>
-            {
-                color = tui::style::Color::Yellow;
-                if big_count == guesses.len() - 1 { is_correct = false; }
-            },
-            1 =>
+            (Some(guess), Some(result)) =>
             {
-                color = tui::style::Color::Red;
-                if big_count == guesses.len() - 1 { is_correct = false; }
-            },
+                for (letter, color) in guess.chars().zip(result.letters())
+                {
+                    line.push((theme.mark(letter, Some(color)), Some(color)));
+                    line.push((" ".to_string(), None));
+                }
+            }
             _ =>
             {
-                color = tui::style::Color::Black;
-                if big_count == guesses.len() - 1 { is_correct = false; }
+                for _ in 0..session.length()
+                {
+                    line.push((theme.mark(' ', None), None));
+                    line.push((" ".to_string(), None));
+                }
             }
         }
-        spans.push(Span::styled(format!("{} ", letter), Style::default().fg(color)));
-        small_count += 1;
+        lines.push(line);
     }
-    output.push(Spans::from(spans));
-    big_count += 1;
+    for row in keyboard.iter()
+    {
+        lines.push(row
+            .chars()
+            .map(|letter|
+            {
+                let color = session.keyboard().get(&letter).copied();
+                match letter
+                {
+                    ' ' => (" ".to_string(), None),
+                    _ => (theme.key_mark(letter, color), color),
+                }
+            })
+            .collect());
     }
-    
-    let keyboard = [
-        "Q W E R T Y U I O P",
-        " A S D F G H J K L",
-        "  Z X C V B N M"
-    ];
+    lines
+}
 
-    for row in keyboard.iter()
+//一行中各段文字的总宽度
+fn line_width(line: &[(String, Option<LetterFeedback>)]) -> usize
+{
+    line.iter().map(|(text, _)| text.chars().count()).sum()
+}
+
+//将多个棋盘的显示内容横向拼接，每组最多 BOARDS_PER_ROW 个，空白不着色
+fn boards_lines(session: &MultiSession, theme: Theme, keyboard: &[String]) -> Vec<Vec<(String, Option<LetterFeedback>)>>
+{
+    let rows = session.guesses().len();
+    let mut lines = Vec::new();
+    for group in session.boards().chunks(BOARDS_PER_ROW)
     {
-        let mut spans: Vec<Span> = Vec::new();
-        for letter in row.chars()
+        let columns: Vec<_> = group.iter().map(|board| board_lines(board, rows, theme, keyboard)).collect();
+        let width = columns.iter().flatten().map(|line| line_width(line)).max().unwrap_or(0);
+        for row in 0..columns[0].len()
         {
-            match best_result.get(&letter)
+            let mut line = vec![(" ".repeat(5), None)];
+            for column in columns.iter()
             {
-                Some(3) => color = tui::style::Color::Green,
-                Some(2) => color = tui::style::Color::Yellow,
-                Some(1) => color = tui::style::Color::Red,
-                Some(_) | None => color = tui::style::Color::Black,
+                let cells = &column[row];
+                line.extend(cells.iter().cloned());
+                line.push((" ".repeat(width - line_width(cells) + 3), None));
             }
-            spans.push(Span::styled(letter.to_string(), Style::default().fg(color)));
+            while line.last().is_some_and(|cell| cell.0.trim().is_empty())
+            {
+                line.pop();
+            }
+            lines.push(line);
         }
-        output.push(Spans::from(spans));
+        lines.push(Vec::new());
     }
-    (best_result, is_correct)
+    lines
 }
 
-//随机答案
-pub fn random_answer(answer_file: &mut Vec<String>, day: &mut u64, seed: &u64) -> Result<Word, String>
+//用户模式输出全部棋盘，只有一个棋盘时与普通模式相同
+pub fn user_show_boards(session: &MultiSession, theme: Theme, keyboard: &[String])
 {
-    let mut rng = StdRng::seed_from_u64(*seed);
-    if answer_file.is_empty()
+    if let [board] = session.boards()
     {
-        let mut copy= FINAL.to_vec();
-        copy.shuffle(&mut rng);
-        if *day > copy.len() as u64
-        {
-            return Err("INVALID COMMAND LINE: DAY".to_string());
-        }
-        else
+        return user_show(board, theme, keyboard);
+    }
+    for line in boards_lines(session, theme, keyboard)
+    {
+        for (text, color) in line
         {
-            let word = copy[(*day - 1) as usize];
-            *day = (*day + 1) % copy.len() as u64;
-            return gen_answer(word, answer_file);
+            match text.trim().is_empty()
+            {
+                true => print!("{}", text),
+                false => user_print(theme, &text, color),
+            }
         }
+        println!();
     }
-    else
+}
+
+//按主题输出一段带颜色的文字，未猜过的字母使用终端默认颜色
+fn user_print(theme: Theme, text: &str, color: Option<LetterFeedback>)
+{
+    let mut stdout = stdout();
+    match color.and_then(|color| theme.color(color))
     {
-        let copy = answer_file.clone();
-        answer_file.shuffle(&mut rng);
-        if *day > answer_file.len() as u64
+        Some(color) if theme.bold() =>
         {
-            return Err("INVALID COMMAND LINE: DAY".to_string());
+            let _ = execute!(stdout, SetAttribute(Attribute::Bold), SetForegroundColor(color), Print(text), ResetColor, SetAttribute(Attribute::Reset));
         }
-        else
+        Some(color) =>
         {
-            let word = answer_file[(*day - 1) as usize].clone();
-            *answer_file = copy;
-            *day = (*day + 1) % answer_file.len() as u64;
-            return gen_answer(&word, answer_file);
+            let _ = execute!(stdout, SetForegroundColor(color), Print(text), ResetColor);
         }
+        None => print!("{}", text),
     }
 }
 
-//两个需要的比较函数
-pub fn cmp_ref<T: Ord>(a: &(&String, &T), b: &(&String, &T)) -> Ordering
+//随机答案：按种子打乱词库后，第 day 天取第 day 组的 boards 个单词，之后 day 加一
+pub fn random_answers(answer_file: &[String], day: &mut u64, seed: &u64, boards: usize) -> Result<Vec<Word>, WordleError>
 {
-    if a.1 == b.1
+    let mut rng = StdRng::seed_from_u64(*seed);
+    let mut copy: Vec<String> = if answer_file.is_empty()
     {
-        return b.0.cmp(a.0);
+        FINAL.iter().map(|word| word.to_string()).collect()
     }
     else
     {
-        return a.1.cmp(b.1);
+        answer_file.to_vec()
+    };
+    copy.shuffle(&mut rng);
+    if boards > copy.len()
+    {
+        return Err(WordleError::bad_argument("--boards", "MORE BOARDS THAN ANSWERS"));
+    }
+    if *day == 0 || *day > copy.len() as u64
+    {
+        return Err(WordleError::bad_argument("--day", &format!("MUST BE FROM 1 TO {}", copy.len())));
     }
+    let start = (*day - 1) as usize * boards;
+    *day = *day % copy.len() as u64 + 1;
+    (0..boards)
+        .map(|i| gen_answer(&copy[(start + i) % copy.len()], answer_file))
+        .collect()
 }
 
+//按第二项从大到小排序
 pub fn cmp_val<X, T: PartialOrd>(a: &(X, T), b: &(X, T)) -> Ordering
 {
     if let Some(order) = b.1.partial_cmp(&a.1)
@@ -603,147 +434,3 @@ pub fn cmp_val<X, T: PartialOrd>(a: &(X, T), b: &(X, T)) -> Ordering
         Ordering::Greater
     }
 }
-
-//TUI输出STRING的两个函数
-fn draw_ui_str<B: Backend>(terminal: &mut Terminal<B>, input: &str, output: &str) -> Result<(), io::Error> 
-{
-    terminal.draw(|f| 
-        {
-        let size = f.size();
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(2)
-            .constraints(
-                [
-                    Constraint::Length(3),
-                    Constraint::Min(1),
-                    Constraint::Length(3),
-                ]
-                .as_ref(),
-            )
-            .split(size);
-
-        //输入框
-        let input_widget = Paragraph::new(input)
-            .style(Style::default().fg(tui::style::Color::Blue))
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(input_widget, chunks[0]);
-
-        //输出框
-        let output_widget = Paragraph::new(output)
-            .style(Style::default().fg(tui::style::Color::White))
-            .block(Block::default().borders(Borders::ALL));
-        f.render_widget(output_widget, chunks[1]);
-    })?;
-
-    Ok(())
-}
-
-pub fn get_input_str<B: Backend>(terminal: &mut Terminal<B>, output: &str) -> Result<String, io::Error>
-{
-    let mut input = String::new();
-    loop
-    {
-        draw_ui_str(terminal, &input, output)?;
-
-        if let Event::Key(event) = event::read()?//处理输入
-        {
-            match event.code
-            {
-                KeyCode::Char(c) => 
-                {
-                    if input.len() < 5 
-                    {
-                        input.push(c);
-                    }
-                }
-                KeyCode::Backspace => 
-                {
-                    input.pop();
-                }
-                KeyCode::Enter => 
-                {
-                    break;
-                }
-                KeyCode::Esc => 
-                {
-                    panic!(); // Exit the game loop on Esc key
-                }
-                _ => {}
-            }
-        }    
-    }
-    Ok(input)
-}
-
-//TUI输出SPANS的两个函数
-pub fn get_input_span<B: Backend>(terminal: &mut Terminal<B>, output: &Vec<Spans>) -> Result<String, io::Error>
-{
-    let mut input = String::new();
-    loop
-    {
-        draw_ui_span(terminal, &input, output)?;
-
-        if let Event::Key(event) = event::read()?//处理输入
-        {
-            match event.code
-            {
-                KeyCode::Char(c) => 
-                {
-                    if input.len() < 5 
-                    {
-                        input.push(c);
-                    }
-                }
-                KeyCode::Backspace => 
-                {
-                    input.pop();
-                }
-                KeyCode::Enter => 
-                {
-                    break;
-                }
-                KeyCode::Esc => 
-                {
-                    panic!();
-                }
-                _ => {}
-            }
-        }    
-    }
-    Ok(input)
-}
-
-fn draw_ui_span<B: Backend>(terminal: &mut Terminal<B>, input: &str, output: &Vec<Spans>) -> Result<(), io::Error> 
-{
-    terminal.draw(|f| 
-        {
-        let size = f.size();
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(2)
-            .constraints(
-                [
-                    Constraint::Length(3),
-                    Constraint::Min(1),
-                    Constraint::Length(3),
-                ]
-                .as_ref(),
-            )
-            .split(size);
-
-        //输入框
-        let input_widget = Paragraph::new(input)
-            .style(Style::default().fg(tui::style::Color::Blue))
-            .block(Block::default().borders(Borders::ALL).title("INPUT"));
-        f.render_widget(input_widget, chunks[0]);
-
-        //输出框
-        let output_widget = Paragraph::new(output.clone())
-            .style(Style::default().fg(tui::style::Color::White))
-            .block(Block::default().borders(Borders::ALL).title("OUTPUT"));
-        f.render_widget(output_widget, chunks[1]);
-    })?;
-
-    Ok(())
-}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use std::collections::{HashMap, HashSet};
+use crate::feedback::{LetterFeedback, Pattern};
+
+//难度：普通、困难、极难
+//困难模式要求保留所有已知的绿色字母，并包含足够数量的黄色字母
+//极难模式还禁止使用已知不存在的字母（或超过已知数量），以及出现在已知错误的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Difficulty
+{
+    Normal,
+    Hard,
+    UltraHard,
+}
+
+//由之前所有猜测累积得到的限制
+#[derive(Debug, Clone)]
+pub struct HardModeRules
+{
+    strict: bool,
+    greens: Vec<Option<char>>,
+    min_counts: HashMap<char, usize>,
+    max_counts: HashMap<char, usize>,
+    banned: Vec<HashSet<char>>,
+}
+
+impl HardModeRules
+{
+    pub fn new(strict: bool) -> HardModeRules
+    {
+        HardModeRules
+        {
+            strict,
+            greens: Vec::new(),
+            min_counts: HashMap::new(),
+            max_counts: HashMap::new(),
+            banned: Vec::new(),
+        }
+    }
+
+    //普通难度时没有限制
+    pub fn for_difficulty(difficulty: Difficulty) -> Option<HardModeRules>
+    {
+        match difficulty
+        {
+            Difficulty::Normal => None,
+            Difficulty::Hard => Some(HardModeRules::new(false)),
+            Difficulty::UltraHard => Some(HardModeRules::new(true)),
+        }
+    }
+
+    //记录一次猜测及其结果
+    pub fn record(&mut self, guess: &str, result: Pattern)
+    {
+        let length = result.length();
+        if self.greens.len() < length
+        {
+            self.greens.resize(length, None);
+            self.banned.resize(length, HashSet::new());
+        }
+        let mut found: HashMap<char, usize> = HashMap::new();
+        let mut absent: HashSet<char> = HashSet::new();
+        for (count, (letter, color)) in guess.chars().zip(result.letters()).enumerate()
+        {
+            match color
+            {
+                LetterFeedback::Correct =>
+                {
+                    self.greens[count] = Some(letter);
+                    *found.entry(letter).or_insert(0) += 1;
+                }
+                LetterFeedback::Present =>
+                {
+                    self.banned[count].insert(letter);
+                    *found.entry(letter).or_insert(0) += 1;
+                }
+                LetterFeedback::Absent =>
+                {
+                    self.banned[count].insert(letter);
+                    absent.insert(letter);
+                }
+            }
+        }
+        for (&letter, &count) in found.iter()
+        {
+            let min = self.min_counts.entry(letter).or_insert(0);
+            *min = (*min).max(count);
+        }
+        //同一个字母既有命中又有红色时，其数量被精确确定
+        for letter in absent
+        {
+            self.max_counts.insert(letter, found.get(&letter).copied().unwrap_or(0));
+        }
+    }
+
+    //检查猜测是否满足全部限制，不满足时返回具体原因
+    pub fn check(&self, guess: &str) -> Result<(), String>
+    {
+        let letters: Vec<char> = guess.chars().collect();
+        for (count, green) in self.greens.iter().enumerate()
+        {
+            if let Some(green) = green
+            {
+                if letters.get(count) != Some(green)
+                {
+                    return Err(format!("{} LETTER MUST BE {}", ordinal(count + 1), green));
+                }
+            }
+        }
+
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for &letter in letters.iter()
+        {
+            *counts.entry(letter).or_insert(0) += 1;
+        }
+        let mut min_counts: Vec<(&char, &usize)> = self.min_counts.iter().collect();
+        min_counts.sort();
+        for (letter, &min) in min_counts
+        {
+            if counts.get(letter).copied().unwrap_or(0) < min
+            {
+                return Err(match min
+                {
+                    1 => format!("GUESS MUST CONTAIN {}", letter),
+                    _ => format!("GUESS MUST CONTAIN {} {}", min, letter),
+                });
+            }
+        }
+
+        if !self.strict
+        {
+            return Ok(());
+        }
+        let mut max_counts: Vec<(&char, &usize)> = self.max_counts.iter().collect();
+        max_counts.sort();
+        for (letter, &max) in max_counts
+        {
+            if counts.get(letter).copied().unwrap_or(0) > max
+            {
+                return Err(match max
+                {
+                    0 => format!("GUESS MUST NOT CONTAIN {}", letter),
+                    _ => format!("GUESS MUST CONTAIN AT MOST {} {}", max, letter),
+                });
+            }
+        }
+        for (count, letter) in letters.iter().enumerate()
+        {
+            if self.banned.get(count).is_some_and(|banned| banned.contains(letter))
+            {
+                return Err(format!("{} LETTER CANNOT BE {}", ordinal(count + 1), letter));
+            }
+        }
+        Ok(())
+    }
+}
+
+//序数词：1ST、2ND、3RD、4TH……
+fn ordinal(number: usize) -> String
+{
+    let suffix = match (number % 10, number % 100)
+    {
+        (_, 11..=13) => "TH",
+        (1, _) => "ST",
+        (2, _) => "ND",
+        (3, _) => "RD",
+        _ => "TH",
+    };
+    format!("{}{}", number, suffix)
+}
@@ -0,0 +1,240 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::TcpStream;
+use tui::backend::Backend;
+use tui::Terminal;
+use crate::error::WordleError;
+use crate::game::{self, Word};
+use crate::screen::{self, Action, Screen};
+use crate::session::{MultiSession, Status};
+
+//输入来源：标准输入、TUI、预先写好的脚本或网络连接
+//所有前端共用 read_answer / read_guess / read_yes_no 中的检查与报错
+pub trait InputSource
+{
+    //显示提示并读取一行，limit 为最多输入的字符数，输入结束时返回 None
+    fn read_line(&mut self, prompt: &str, limit: usize) -> io::Result<Option<String>>;
+
+    //报告一条不合法输入的原因
+    fn warn(&mut self, message: &str);
+}
+
+//读取一个合法的答案，输入结束时返回 None
+pub fn read_answer<I: InputSource>(input: &mut I, answer_file: &[String], length: usize) -> io::Result<Option<Word>>
+{
+    loop
+    {
+        let word = match input.read_line("CHOOSE AN ANSWER:", length)?
+        {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+        match game::gen_answer(word.trim(), answer_file)
+        {
+            Ok(answer) => return Ok(Some(answer)),
+            Err(error) => input.warn(&error.to_string()),
+        }
+    }
+}
+
+//读取一个合法的猜测并提交，输入结束时返回 None
+pub fn read_guess<I: InputSource>(input: &mut I, session: &mut MultiSession) -> io::Result<Option<Status>>
+{
+    loop
+    {
+        let word = match input.read_line("CHOOSE A GUESS:", session.length())?
+        {
+            Some(word) => word,
+            None => return Ok(None),
+        };
+        match session.submit_guess(&word)
+        {
+            Ok(status) => return Ok(Some(status)),
+            Err(error) => input.warn(&error.to_string()),
+        }
+    }
+}
+
+//读取 Y / N，输入结束视为 N，其他输入为错误
+pub fn read_yes_no<I: InputSource>(input: &mut I, prompt: &str) -> Result<bool, WordleError>
+{
+    match input.read_line(prompt, 1)?
+    {
+        None => Ok(false),
+        Some(line) => match line.trim()
+        {
+            "Y" | "y" => Ok(true),
+            "N" | "n" => Ok(false),
+            _ => Err(WordleError::InvalidInput("INVALID INPUT".to_string())),
+        },
+    }
+}
+
+//标准输入，测试模式下不显示提示，且所有不合法输入都只报告 INVALID
+pub struct StdinInput
+{
+    prompts: bool,
+    detailed: bool,
+}
+
+impl StdinInput
+{
+    pub fn test() -> StdinInput
+    {
+        StdinInput{ prompts: false, detailed: false }
+    }
+
+    pub fn interactive() -> StdinInput
+    {
+        StdinInput{ prompts: true, detailed: true }
+    }
+}
+
+impl InputSource for StdinInput
+{
+    fn read_line(&mut self, prompt: &str, _limit: usize) -> io::Result<Option<String>>
+    {
+        if self.prompts
+        {
+            println!("{}", prompt);
+        }
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line)?
+        {
+            0 => Ok(None),
+            _ => Ok(Some(line)),
+        }
+    }
+
+    fn warn(&mut self, message: &str)
+    {
+        if self.detailed
+        {
+            println!("{}", message);
+        }
+        else
+        {
+            println!("INVALID");
+        }
+    }
+}
+
+//TUI 输入，提示与警告显示在状态栏中，开启动画时不合法的单词改为抖动
+//按下操作键时读取中断并返回 None，由调用者通过 take_action 取得该操作
+pub struct TuiInput<B: Backend>
+{
+    terminal: Terminal<B>,
+    pub screen: Screen,
+    action: Option<Action>,
+    last: String,
+}
+
+impl<B: Backend> TuiInput<B>
+{
+    pub fn new(terminal: Terminal<B>) -> TuiInput<B>
+    {
+        TuiInput{ terminal, screen: Screen::new(), action: None, last: String::new() }
+    }
+
+    //取出上一次中断读取的操作
+    pub fn take_action(&mut self) -> Option<Action>
+    {
+        self.action.take()
+    }
+}
+
+impl<B: Backend> InputSource for TuiInput<B>
+{
+    fn read_line(&mut self, prompt: &str, limit: usize) -> io::Result<Option<String>>
+    {
+        self.screen.prompt = prompt.to_string();
+        match screen::read_input(&mut self.terminal, &mut self.screen, limit)?
+        {
+            Ok(line) =>
+            {
+                self.last = line.clone();
+                Ok(Some(line))
+            }
+            Err(action) =>
+            {
+                self.action = Some(action);
+                Ok(None)
+            }
+        }
+    }
+
+    fn warn(&mut self, message: &str)
+    {
+        self.screen.reject(&self.last, message);
+    }
+}
+
+//按顺序给出预先写好的输入，用于测试，警告被记录下来
+pub struct ScriptedInput<T: Iterator<Item = String>>
+{
+    lines: T,
+    pub warnings: Vec<String>,
+}
+
+impl<T: Iterator<Item = String>> ScriptedInput<T>
+{
+    pub fn new<I: IntoIterator<IntoIter = T>>(lines: I) -> ScriptedInput<T>
+    {
+        ScriptedInput{ lines: lines.into_iter(), warnings: Vec::new() }
+    }
+}
+
+impl<T: Iterator<Item = String>> InputSource for ScriptedInput<T>
+{
+    fn read_line(&mut self, _prompt: &str, _limit: usize) -> io::Result<Option<String>>
+    {
+        Ok(self.lines.next())
+    }
+
+    fn warn(&mut self, message: &str)
+    {
+        self.warnings.push(message.to_string());
+    }
+}
+
+//任意字节流，例如网络连接：提示与警告写回对方，每行为一次输入
+pub struct StreamInput<R: BufRead, W: Write>
+{
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> StreamInput<R, W>
+{
+    pub fn new(reader: R, writer: W) -> StreamInput<R, W>
+    {
+        StreamInput{ reader, writer }
+    }
+}
+
+impl StreamInput<BufReader<TcpStream>, TcpStream>
+{
+    pub fn tcp(stream: TcpStream) -> io::Result<StreamInput<BufReader<TcpStream>, TcpStream>>
+    {
+        Ok(StreamInput::new(BufReader::new(stream.try_clone()?), stream))
+    }
+}
+
+impl<R: BufRead, W: Write> InputSource for StreamInput<R, W>
+{
+    fn read_line(&mut self, prompt: &str, _limit: usize) -> io::Result<Option<String>>
+    {
+        writeln!(self.writer, "{}", prompt)?;
+        self.writer.flush()?;
+        let mut line = String::new();
+        match self.reader.read_line(&mut line)?
+        {
+            0 => Ok(None),
+            _ => Ok(Some(line)),
+        }
+    }
+
+    fn warn(&mut self, message: &str)
+    {
+        let _ = writeln!(self.writer, "{}", message);
+    }
+}
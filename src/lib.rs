@@ -0,0 +1,28 @@
+//Wordle 核心库：单词比较、游戏状态机、求解器、存档、回放与分析
+pub mod analysis;
+pub mod builtin_words;
+pub mod config;
+pub mod dictionary;
+pub mod error;
+pub mod feedback;
+pub mod file;
+pub mod game;
+pub mod hard_mode;
+pub mod input;
+pub mod matrix;
+pub mod replay;
+pub mod screen;
+pub mod session;
+pub mod share;
+pub mod solver;
+pub mod theme;
+pub mod wordlist;
+
+pub use error::WordleError;
+pub use feedback::{LetterFeedback, Pattern};
+pub use game::Word;
+pub use hard_mode::{Difficulty, HardModeRules};
+pub use input::InputSource;
+pub use session::{GameSession, Outcome, Status};
+pub use share::ShareFormat;
+pub use theme::Theme;
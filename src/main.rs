@@ -1,678 +1,944 @@
-use game::{Word, get_input_span};
-use std::{collections::HashMap, fmt};
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
-use crossterm::execute;
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
-use std::io;
+use std::cell::OnceCell;
+use std::io::{self, Write};
+use std::process::ExitCode;
 use tui::backend::CrosstermBackend;
 use tui::style::Style;
 use tui::text::{Span, Spans};
 use tui::Terminal;
+use clap::Parser;
 use text_io::read;
+use wordle::feedback::Pattern;
+use wordle::builtin_words::{ACCEPTABLE, FINAL};
+use wordle::game::{self, Word};
+use wordle::hard_mode::Difficulty;
+use wordle::input::{self, InputSource, StdinInput, TuiInput};
+use wordle::screen::{self, Action, Screen, TerminalGuard};
+use wordle::matrix::FeedbackMatrix;
+use wordle::session::{self, GameSession, MultiSession, Status};
+use wordle::analysis;
+use wordle::replay::{self, ReplayStep};
+use wordle::wordlist::{self, WordList};
+use wordle::dictionary::{self, Dictionary};
+use wordle::{file, share, solver, Theme, WordleError};
 
 mod arg;
-mod game;
-mod builtin_words;
-mod file;
-mod solver;
-
-//定义报错
-#[derive(Debug)]
-struct MyError
-{
-    source: String,
-}
-impl fmt::Display for MyError
-{
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+use arg::{Commands, ConfigCommand, DictionaryCommand, WordlistCommand};
+
+fn main() -> ExitCode {
+
+    //出错时在标准错误中给出原因，退出码由错误的种类决定
+    match run()
     {
-        write!(f, "{}", self.source)
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) =>
+        {
+            eprintln!("Error: {}", error);
+            ExitCode::from(error.exit_code())
+        }
     }
 }
-impl std::error::Error for MyError{}
-
-#[cfg(feature = "play")]
-fn main() -> Result<(), Box<dyn std::error::Error>> {
 
+fn run() -> Result<(), WordleError>
+{
     let is_tty = atty::is(atty::Stream::Stdout);
 
-    //处理命令行
-    let mut cmd = match arg::process_arg()
-    {
-        Ok(tmp) => tmp,
-        Err(error) => return Err(Box::new(MyError{ source: error, }))
-    };
-    if !arg::arg_is_valid(&cmd)
+    //处理命令行，不带子命令时开始游戏
+    let cli = arg::Cli::parse();
+    match cli.command
     {
-        return Err(Box::new(MyError{ source: "INVALID COMMAND LINE LOGIC".to_string(), }));
+        None => play(cli.play, is_tty),
+        Some(Commands::Play(args)) => play(args, is_tty),
+        Some(Commands::Solve(args)) =>
+        {
+            solve(&command(args.play_args())?);
+            Ok(())
+        }
+        Some(Commands::Bench{ max_guesses, theme }) =>
+        {
+            //主题与其他命令一样取自参数、环境变量与配置文件
+            let cmd = command(arg::PlayArgs{ theme, ..arg::PlayArgs::default() })?;
+            solver::test(max_guesses, cmd.theme);
+            Ok(())
+        }
+        Some(Commands::Stats{ state }) =>
+        {
+            print_stats(&file::read_state(&state)?);
+            Ok(())
+        }
+        Some(Commands::Replay(saved)) =>
+        {
+            let cmd = command(saved.play_args())?;
+            replay_games(&cmd, saved.game, &load_state(&cmd)?, is_tty)
+        }
+        Some(Commands::Analyze{ saved, json }) =>
+        {
+            let cmd = command(saved.play_args())?;
+            analyze_game(&cmd, saved.game, json, &load_state(&cmd)?)
+        }
+        Some(Commands::Config{ command: ConfigCommand::Show(args) }) =>
+        {
+            let layers = args.layers()?;
+            print!("{}", layers);
+            Ok(())
+        }
+        Some(Commands::Wordlist{ command }) => wordlist_command(command),
+        Some(Commands::Dictionary{ command }) => dictionary_command(command),
     }
+}
 
-    //全局参数
-    let mut answer: game::Word = Word::new("");
-    let mut guess: game::Word = Word::new("");
-    let mut again: bool = true;
-    let mut total_round: i32 = 0;
-    let mut success_round: i32 = 0;
-    let mut success_try: i32 = 0;
-    let mut total_word: HashMap<String, i32> = HashMap::new();
-    let mut difficult: bool = false;
-    let mut game_data: file::GameData = file::GameData
-    {
-        total_rounds: 0,
-        games: Vec::new(),
-    };
-    if let Some(_i) = cmd.mode.get("difficult")
-    {
-        difficult = true;
-    }
-    let mut day: u64 = 1;
-    let mut seed: u64 = 1;
-    if let Some(j) = cmd.value.get("day")
+//合并配置文件并检查参数间的冲突
+fn command(args: arg::PlayArgs) -> Result<arg::Command, WordleError>
+{
+    let cmd = arg::Command::new(args)?;
+    arg::check_logic(&cmd)?;
+    Ok(cmd)
+}
+
+//读取游戏存档，没有指定存档时为空
+fn load_state(cmd: &arg::Command) -> Result<file::GameData, WordleError>
+{
+    let state = match &cmd.state
     {
-        day = *j;
-    }
-    if let Some(k) = cmd.value.get("seed")
+        Some(state) => state,
+        None => return Ok(file::GameData::default()),
+    };
+    let game_data = file::read_state(state)?;
+    let invalid = |reason: String| WordleError::State{ path: state.clone(), position: None, reason };
+    if game_data.total_rounds as usize != game_data.games.len()
     {
-        seed = *k;
+        return Err(invalid(format!("TOTAL ROUNDS IS {} BUT THERE ARE {} GAMES", game_data.total_rounds, game_data.games.len())));
     }
-
-    //读取游戏存档
-    if let Some(state) = cmd.info.get("state")
+    for (index, round) in game_data.games.iter().enumerate()
     {
-        if let Ok(tmp) = file::read_state(state)
+        if round.guesses.len() > session::total_guesses(round.all_answers().len(), cmd.max_guesses)
         {
-            game_data = tmp;
-            total_round = game_data.total_rounds;
-            if game_data.total_rounds as usize != game_data.games.len()
-            {
-                return Err(Box::new(MyError{source: "INVALID STATE".to_string()}));
-            }
-            for i in game_data.games.iter()
-            {
-                if i.guesses.len() > 6
-                {
-                    return Err(Box::new(MyError{source: "INVALID STATE".to_string()}));
-                }
-                match i.guesses.last()
-                {
-                    Some(j) =>
-                    {
-                        if i.answer == *j
-                        {
-                            success_round += 1;
-                            success_try += i.guesses.len() as i32;
-                        }
-                    }
-                    None => (),
-                }
-                for j in i.guesses.iter()
-                {
-                    let count = total_word.entry(j.to_string()).or_insert(0);
-                    *count += 1;
-                }
-            }
+            return Err(invalid(format!("GAME {} HAS TOO MANY GUESSES", index + 1)));
         }
-        else
+        if !round.answer.is_empty() && round.guesses.iter().any(|guess| guess.chars().count() != round.answer.chars().count())
         {
-            return Err(Box::new(MyError{source: "INVALID STATE".to_string()}));
+            return Err(invalid(format!("GAME {} HAS GUESSES OF THE WRONG LENGTH", index + 1)));
         }
     }
+    Ok(game_data)
+}
+
+//游戏：测试模式、TUI模式与用户模式
+fn play(args: arg::PlayArgs, is_tty: bool) -> Result<(), WordleError> {
+
+    let mut cmd = command(args)?;
+
+    //全局参数
+    let mut again: bool = true;
+    let difficulty = cmd.difficulty;
+    let mut day: u64 = cmd.day.unwrap_or(1);
+    let seed: u64 = cmd.seed.unwrap_or(1);
+    let mut game_data = load_state(&cmd)?;
 
 //主体
 if !is_tty//测试模式
 {
+let mut stdin = StdinInput::test();
 while again
 {
-    total_round += 1;
-    game_data.total_rounds += 1;
-
     //生成答案
-    if let Some(_i) = cmd.mode.get("random")
-    {
-        match game::random_answer(&mut cmd.answer_file, &mut day, &seed)
-        {
-            Ok(tmp) => answer = tmp,
-            Err(tmp) => return Err(Box::new(MyError{source: tmp}))
-        }
-    }
-    else
+    let round_day = day;
+    let answers = match fixed_answer(&mut cmd, &mut day, seed, &mut again)?
     {
-        if let Some(i) = cmd.info.get("word")
+        Some(tmp) => tmp,
+        None => match read_answers(&mut stdin, &cmd)?
         {
-            match game::gen_answer(i, &cmd.answer_file)
-            {
-                Ok(tmp) => answer = tmp,
-                Err(_tmp) => return Err(Box::new(MyError{source: "INVALID WORD".to_string()})),
-            }
-            again = false;
-        }
-        else
-        {
-            answer = game::std_answer(&cmd.answer_file);
-        }
-    }
-
-    //记录每局信息
-    let mut record: Vec<String> = Vec::new();
-    let mut this_round: file::Round = file::Round { answer: answer.origin.clone(), guesses: Vec::new() };
+            Some(tmp) => tmp,
+            None => break,
+        },
+    };
 
     //开始猜测
-    let mut result: HashMap<char, u8> = HashMap::new();
-    let mut is_correct: bool = false;
-    let mut tmp_result: [u8; 5] = [0; 5];
-    for i in 1..=6
-    {
-        //获取猜测
-        guess = game::std_guess(&cmd.guess_file, &tmp_result, &record, &difficult);
-
-        //更新统计数据
-        let tmp = guess.origin.clone();
-        record.push(tmp.clone());
-        this_round.guesses.push(tmp.clone());
-        if i != 1 { game_data.games.pop(); }
-        game_data.games.push(this_round.clone());
-        let count = total_word.entry(tmp.clone()).or_insert(0);
-        *count += 1;
-
-        if let Some(state) = cmd.info.get("state")
-        {
-            if let Err(_) = file::write_state(&game_data, state)
-            {
-                return Err(Box::new(MyError{source: "FAILED TO WRITE STATE".to_string()}));
-            }
-        }
+    let mut session = new_session(&cmd, answers, difficulty);
+    while !session.is_over()
+    {
+        let outcome = match input::read_guess(&mut stdin, &mut session)?
+        {
+            Some(tmp) => tmp,
+            None => break,
+        };
+        game_data.update(session.round());
+        save_state(&cmd, &game_data)?;
 
-        //更新结果
-        tmp_result = answer.compare(&guess.origin);
-        (result, is_correct) = game::test_update_and_show(&guess.origin, &tmp_result, result);
-        if is_correct
+        game::test_show_boards(&session, &cmd.dictionary.alphabet);
+        match outcome
         {
-            println!("CORRECT {}", i);
-            success_try += i;
-            success_round += 1;
-            break;
+            Status::Won => println!("CORRECT {}", session.guesses().len()),
+            Status::Lost => println!("FAILED {}", session.answers().join(" ")),
+            Status::Playing => (),
         }
     }
-    if !is_correct
+    if !session.is_over()
     {
-        println!("FAILED {}", answer.origin);
+        break;
+    }
+    if cmd.share.is_some()
+    {
+        print!("{}", share_text(&cmd, &session, round_day, difficulty));
     }
-
-    //更新游戏存档
 
     //打印数据
-    if let Some(_i) = cmd.mode.get("stats")
+    if cmd.stats
     {
-        let mut average: f32 = 0.0;
-        if success_round != 0
-        {
-            average = (success_try as f32) / (success_round as f32);
-        }
-        println!("{} {} {:.2}", success_round, total_round - success_round, average);
-        let mut vec: Vec<(&String, &i32)> = total_word.iter().collect();
-        vec.sort_by(|a, b| game::cmp_ref(b, a));
-        let end = if vec.len() < 5 { vec.len() } else { 5 };
-        for i in 0..(end - 1)
-        {
-            print!("{} {} ", vec[i].0, vec[i].1);
-        }
-        println!("{} {}", vec[end - 1].0, vec[end - 1].1);
+        print_stats(&game_data);
     }
 
     //是否再来
     if again
     {
-        let mut another = "".to_string();
-        match std::io::stdin().read_line(&mut another)
-        {
-            Ok(0) => again = false,
-            Err(error) => println!("{}", error),
-            Ok(_) =>
-            {
-                match another.trim()
-                {
-                    a if a == "Y" || a == "y" => (),
-                    b if b == "N" || b == "n" => again = false,
-                    _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-                }
-            }
-        }
+        again = yes_no(&mut stdin, "WANT ANOTHER ROUND? [Y / N]")?;
     }
 }//while结束
 }
 else//交互模式
 {
     //是否使用UI
-    println!("WANT TUI? [Y / N]");
-    let mut ui = "".to_string();
-    let mut is_ui = false;
-    match std::io::stdin().read_line(&mut ui)
+    let mut stdin = StdinInput::interactive();
+    let is_ui = yes_no(&mut stdin, "WANT TUI? [Y / N]")?;
+
+    //提示所用的结果矩阵，第一次需要提示时才计算
+    let guess_list = word_list(&cmd.guess_file, ACCEPTABLE);
+    let answer_list = word_list(&cmd.answer_file, FINAL);
+    let is_builtin = cmd.guess_file.is_empty() && cmd.answer_file.is_empty();
+    let custom_matrix: OnceCell<FeedbackMatrix> = OnceCell::new();
+    let matrix = ||
     {
-        Ok(0) => is_ui = false,
-        Err(error) => println!("{}", error),
-        Ok(_) =>
+        if is_builtin
         {
-            match ui.trim()
-            {
-                a if a == "Y" || a == "y" => is_ui = true,
-                b if b == "N" || b == "n" => is_ui = false,
-                _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-            }
+            FeedbackMatrix::builtin()
         }
-    }
+        else
+        {
+            custom_matrix.get_or_init(|| FeedbackMatrix::cached(&guess_list, &answer_list))
+        }
+    };
 
 if is_ui//TUI模式
 {
-    //初始化终端等
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-    let mut str_output = String::new();
-    let mut span_output: Vec<Spans> = Vec::new();
+    //初始化终端等，guard 离开作用域或 panic 时恢复终端
+    let guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut tui = TuiInput::new(Terminal::new(backend)?);
+    tui.screen.animate = !cmd.no_animation;
+    tui.screen.theme = cmd.theme;
+    tui.screen.layout = cmd.dictionary.keyboard.clone();
+    let mut summary: Vec<String> = Vec::new();
+    let mut shares: Vec<String> = Vec::new();
 
     while again
     {
         //确保开始时输出为空
-        str_output.clear();
-        span_output.clear();
+        tui.screen.clear();
 
-        game_data.total_rounds += 1;
-        total_round += 1;
-    
-        //生成答案
-        if let Some(_i) = cmd.mode.get("random")
+        //生成答案，输入时按下操作键则重新开始
+        let round_day = day;
+        let answers = match fixed_answer(&mut cmd, &mut day, seed, &mut again)?
         {
-            match game::random_answer(&mut cmd.answer_file, &mut day, &seed)
+            Some(tmp) => tmp,
+            None => match read_answers(&mut tui, &cmd)?
             {
-                Ok(tmp) => answer = tmp,
-                Err(tmp) => return Err(Box::new(MyError{source: tmp}))
-            }
-        }
-        else
+                Some(tmp) => tmp,
+                None if tui.take_action() == Some(Action::Quit) => break,
+                None => continue,
+            },
+        };
+
+        //开始猜测，? 显示或隐藏提示，CTRL-N 放弃本局，ESC 退出
+        let mut session = new_session(&cmd, answers, difficulty);
+        let mut quit = false;
+        tui.screen.show(&session);
+        while !session.is_over()
         {
-            if let Some(i) = cmd.info.get("word")
+            let outcome = match input::read_guess(&mut tui, &mut session)?
             {
-                match game::gen_answer(i, &cmd.answer_file)
+                Some(tmp) => tmp,
+                None => match tui.take_action()
                 {
-                    Ok(tmp) => answer = tmp,
-                    Err(_tmp) => return Err(Box::new(MyError{source: "INVALID WORD".to_string()})),
-                }
-                again = false;
+                    Some(Action::Hint) =>
+                    {
+                        if tui.screen.side.is_empty()
+                        {
+                            if let Some(board) = session.current_board()
+                            {
+                                tui.screen.side_title = "HINTS".to_string();
+                                tui.screen.side = hint_lines(matrix(), board, tui.screen.theme);
+                            }
+                        }
+                        else
+                        {
+                            tui.screen.side.clear();
+                        }
+                        continue;
+                    }
+                    Some(Action::NewGame) if again => break,
+                    Some(Action::Quit) =>
+                    {
+                        quit = true;
+                        break;
+                    }
+                    _ => continue,
+                },
+            };
+
+            //更新记录与结果
+            game_data.update(session.round());
+            save_state(&cmd, &game_data)?;
+
+            tui.screen.show(&session);
+            tui.screen.side.clear();
+            match outcome
+            {
+                Status::Won => tui.screen.message = format!("CORRECT {}", session.guesses().len()),
+                Status::Lost => tui.screen.message = format!("FAILED {}", session.answers().join(" ")),
+                Status::Playing => (),
             }
-            else
+        }
+        if !session.guesses().is_empty()
+        {
+            summary.push(summary_line(summary.len() + 1, &session));
+        }
+        if quit
+        {
+            break;
+        }
+        if !session.is_over()
+        {
+            continue;
+        }
+
+        //打印数据
+        if cmd.stats
+        {
+            let stats = game_data.stats();
+            tui.screen.side_title = "STATISTICS".to_string();
+            tui.screen.side = vec![
+                Spans::from(format!("SUCCESS: {}", stats.success)),
+                Spans::from(format!("FAILED: {}", stats.failed)),
+                Spans::from(format!("AVERAGE: {:.2}", stats.average)),
+                Spans::from(""),
+            ];
+            for (word, count) in stats.words.iter().take(5)
             {
-                answer = game::tui_answer(&mut terminal, &cmd.answer_file);
+                tui.screen.side.push(Spans::from(format!("{} {}", word, count)));
             }
         }
-    
-        //记录每局信息
-        let mut record: Vec<String> = Vec::new();
-        let mut this_round: file::Round = file::Round { answer: answer.origin.clone(), guesses: Vec::new() };
-    
-        //开始猜测
-        let mut best_result: HashMap<char, u8> = HashMap::new();
-        let mut is_correct: bool = false;
-        let mut tmp_result: [u8; 5] = [0; 5];
-        let mut full_result: Vec<[u8; 5]> = Vec::new();
-        for i in 1..=6
+
+        //分享结果，指定了 --share 时直接显示，否则按 CTRL-S 显示并复制，退出后一并打印
+        let share = share_text(&cmd, &session, round_day, difficulty);
+        let mut shared = cmd.share.is_some();
+        if shared
         {
-            //是否需要提示
-            let mut is_help = false;
-            span_output.push(Spans::from(vec![Span::raw("WANT SOME HELP? [Y / N]")]));
-            match get_input_span(&mut terminal, &span_output)
+            show_share(&mut tui.screen, &share);
+            shares.push(share.clone());
+        }
+
+        //是否再来：CTRL-N 开始新的一局，CTRL-S 分享，ENTER 退出
+        let prompt = if again { "CTRL-N: NEW GAME  CTRL-S: SHARE  ENTER: QUIT" } else { "CTRL-S: SHARE  ENTER: QUIT" };
+        loop
+        {
+            if tui.read_line(prompt, 0)?.is_some()
             {
-                Err(error) => println!("{}", error),
-                Ok(help) =>
-                {
-                    match help.trim()
-                    {
-                        a if a == "Y" || a == "y" => is_help = true,
-                        b if b == "N" || b == "n" => is_help = false,
-                        _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-                    }
-                }
+                again = false;
+                break;
             }
-            span_output.pop();
-            if is_help
+            match tui.take_action()
             {
-                let (info, help) = solver::help(&record, &full_result);
-                let mut spans: Vec<Span> = Vec::new();
-                //可选词中信息熵最大的
-                spans.push(Span::raw("THE MOST INFORMATIVE GUESSES ARE:"));
-                for i in 0..std::cmp::min(5 as usize, info.len())
-                {
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::styled(format!("{}", info[i].0.clone()), Style::default().fg(tui::style::Color::Red)));
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::raw(info[i].1.to_string()));
-                }
-                span_output.push(Spans::from(spans.clone()));
-                spans.clear();
-                //可能的答案中信息熵最大的
-                spans.push(Span::raw("THE BEST GUESSES ARE:"));
-                for i in 0..std::cmp::min(5 as usize, help.len())
+                Some(Action::NewGame) if again => break,
+                Some(Action::Share) =>
                 {
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::styled(format!("{}", help[i].0.clone()), Style::default().fg(tui::style::Color::Red)));
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::raw(help[i].1.to_string()));
+                    if !shared
+                    {
+                        show_share(&mut tui.screen, &share);
+                        shares.push(share.clone());
+                        shared = true;
+                    }
+                    print!("{}", share::clipboard_sequence(&share));
+                    io::stdout().flush()?;
+                    tui.screen.message = "COPIED TO CLIPBOARD".to_string();
                 }
-                span_output.push(Spans::from(spans.clone()));
-                spans.clear();
-                let all = solver::solve(&record, &full_result, 6 - i);
-                //全局最优的
-                spans.push(Span::raw("THE GLOBAL OPTIMUM GUESSES ARE:"));
-                for i in 0..std::cmp::min(5 as usize, all.len())
+                Some(Action::Quit) =>
                 {
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::styled(format!("{}", all[i].0.clone()), Style::default().fg(tui::style::Color::Red)));
-                    spans.push(Span::raw(" "));
-                    spans.push(Span::raw(all[i].1.to_string()));
+                    again = false;
+                    break;
                 }
-                span_output.push(Spans::from(spans));
+                _ => (),
             }
-            guess = game::tui_guess(&mut terminal, &cmd.guess_file, &tmp_result, &record, &difficult, &mut span_output);
-            span_output.clear();
-    
-            //更新一些记录与结果
-            let tmp = guess.origin.clone();
-            record.push(tmp.clone());
-            this_round.guesses.push(tmp.clone());
-            if i != 1 { game_data.games.pop(); }
-            game_data.games.push(this_round.clone());
-            let count = total_word.entry(tmp.clone()).or_insert(0);
-            *count += 1;
-
-            if let Some(state) = cmd.info.get("state")
+        }
+    }
+
+    //恢复终端后打印本次的游戏记录
+    drop(tui);
+    drop(guard);
+    if !summary.is_empty()
+    {
+        println!("SESSION SUMMARY");
+        for line in summary.iter()
+        {
+            println!("{}", line);
+        }
+    }
+    for share in shares.iter()
+    {
+        println!();
+        print!("{}", share);
+    }
+}
+else//用户模式
+{
+    while again
+    {
+        //生成答案
+        let round_day = day;
+        let answers = match fixed_answer(&mut cmd, &mut day, seed, &mut again)?
+        {
+            Some(tmp) => tmp,
+            None => match read_answers(&mut stdin, &cmd)?
+            {
+                Some(tmp) => tmp,
+                None => break,
+            },
+        };
+
+        //开始猜测
+        let mut session = new_session(&cmd, answers, difficulty);
+        while !session.is_over()
+        {
+            //是否需要提示
+            let is_help = yes_no(&mut stdin, "WANT SOME HELP? [Y / N]")?;
+            if let (true, Some(board)) = (is_help, session.current_board())
             {
-                if let Err(_) = file::write_state(&game_data, state)
+                for (title, words) in hints(matrix(), board)
                 {
-                    return Err(Box::new(MyError{source: "FAILED TO WRITE STATE".to_string()}));
+                    print!("{}", title);
+                    for (word, entropy) in words.iter().take(5)
+                    {
+                        print!(" {}: {:.2}", word, entropy);
+                    }
+                    println!();
                 }
             }
+            let outcome = match input::read_guess(&mut stdin, &mut session)?
+            {
+                Some(tmp) => tmp,
+                None => break,
+            };
+
+            //更新记录与结果
+            game_data.update(session.round());
+            save_state(&cmd, &game_data)?;
 
-            tmp_result = answer.compare(&guess.origin);
-            full_result.push(tmp_result);
-            (best_result, is_correct) = game::tui_update_and_show(&record, &full_result, best_result, &mut span_output);
-            if is_correct
+            game::user_show_boards(&session, cmd.theme, &cmd.dictionary.keyboard);
+            match outcome
             {
-                span_output.push(Spans::from(vec![Span::raw("CORRECT "), Span::raw(i.to_string())]));
-                success_try += i;
-                success_round += 1;
-                break;
+                Status::Won => println!("CORRECT {}", session.guesses().len()),
+                Status::Lost => println!("FAILED {}", session.answers().join(" ")),
+                Status::Playing => (),
             }
         }
-        if !is_correct
+        if !session.is_over()
+        {
+            break;
+        }
+        if cmd.share.is_some()
         {
-            span_output.push(Spans::from(vec![Span::raw("FAILED "), Span::raw(answer.origin.clone())]));
+            print!("{}", share_text(&cmd, &session, round_day, difficulty));
         }
-    
+
         //打印数据
-        if let Some(_i) = cmd.mode.get("stats")
+        if cmd.stats
         {
-            let mut average: f32 = 0.0;
-            if success_round != 0
-            {
-                average = (success_try as f32) / (success_round as f32);
-            }
-            str_output = str_output + &success_round.to_string() + " " + &(total_round - success_round).to_string() + " " + &average.to_string() + "\n";
-            span_output.push(Spans::from(vec!
-                [Span::raw("SUCCESS: "),
-                Span::raw(success_round.to_string()),
-                Span::raw(" "),
-                Span::raw("FAILED: "),
-                Span::raw((total_round - success_round).to_string()),
-                Span::raw(" "),
-                Span::raw("AVERAGE: "),
-                Span::raw(average.to_string())]));
-            let mut vec: Vec<(&String, &i32)> = total_word.iter().collect();
-            vec.sort_by(|a, b| game::cmp_ref(b, a));
-            let end = if vec.len() < 5 { vec.len() } else { 5 };
-            let mut spans: Vec<Span> = Vec::new();
-            for i in 0..end
-            {
-                spans.push(Span::raw(vec[i].0.clone()));
-                spans.push(Span::raw(" "));
-                spans.push(Span::raw(vec[i].1.to_string()));
-                spans.push(Span::raw(" "));
-            }
-            span_output.push(Spans::from(spans));
+            print_stats(&game_data);
         }
-    
+
         //是否再来
         if again
         {
-            span_output.push(Spans::from(vec![Span::raw("WANT ANOTHER ROUND? [Y / N]")]));
-            match get_input_span(&mut terminal, &span_output)
-            {
-                Err(error) => println!("{}", error),
-                Ok(tmp) =>
-                {
-                    match tmp.trim()
-                    {
-                        a if a == "Y" || a == "y" => again = true,
-                        b if b == "N" || b == "n" => again = false,
-                        _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-                    }
-                }
-            }
+            again = yes_no(&mut stdin, "WANT ANOTHER ROUND? [Y / N]")?;
         }
-        else
+    }//while结束
+}
+}
+
+    //存档
+    save_state(&cmd, &game_data)?;
+
+    Ok(())
+}
+
+//由命令行决定的答案，每个棋盘一个，返回 None 时需要由用户输入，对抗模式不需要答案
+//多棋盘时 --word 以逗号分隔各个答案
+fn fixed_answer(cmd: &mut arg::Command, day: &mut u64, seed: u64, again: &mut bool) -> Result<Option<Vec<Word>>, WordleError>
+{
+    if cmd.absurdle
+    {
+        Ok(Some(Vec::new()))
+    }
+    else if cmd.random
+    {
+        Ok(Some(game::random_answers(&cmd.answer_file, day, &seed, cmd.boards)?))
+    }
+    else if let Some(i) = &cmd.word
+    {
+        *again = false;
+        let words: Vec<&str> = i.split(',').collect();
+        if words.len() != cmd.boards
+        {
+            return Err(WordleError::bad_argument("--word", &format!("EXPECTED {} COMMA-SEPARATED ANSWERS", cmd.boards)));
+        }
+        let answers = words
+            .iter()
+            .map(|word| game::gen_answer(word, &cmd.answer_file)
+                .map_err(|_| WordleError::bad_argument("--word", &format!("{} IS NOT IN THE ANSWER LIST", dictionary::uppercase(word)))))
+            .collect::<Result<Vec<Word>, WordleError>>()?;
+        Ok(Some(answers))
+    }
+    else
+    {
+        Ok(None)
+    }
+}
+
+//开始新的一局，对抗模式下答案由全部候选词延迟决定
+fn new_session(cmd: &arg::Command, answers: Vec<Word>, difficulty: Difficulty) -> MultiSession
+{
+    if cmd.absurdle
+    {
+        let candidates = word_list(&cmd.answer_file, FINAL).iter().map(|word| Word::new(word)).collect();
+        MultiSession::adversarial(candidates, &cmd.guess_file, difficulty, cmd.max_guesses)
+    }
+    else
+    {
+        MultiSession::new(answers, &cmd.guess_file, difficulty, cmd.max_guesses)
+    }
+}
+
+//依次读取每个棋盘的答案，输入结束时返回 None
+fn read_answers<I: InputSource>(source: &mut I, cmd: &arg::Command) -> io::Result<Option<Vec<Word>>>
+{
+    let mut answers: Vec<Word> = Vec::new();
+    for _ in 0..cmd.boards
+    {
+        match input::read_answer(source, &cmd.answer_file, cmd.length)?
         {
-            span_output.push(Spans::from(vec![Span::raw("PRESS ENTER TO QUIT")]));
-            get_input_span(&mut terminal, &span_output);
+            Some(tmp) => answers.push(tmp),
+            None => return Ok(None),
         }
+    }
+    Ok(Some(answers))
+}
 
-        //清理输出
-        str_output.clear();
-        span_output.clear();
+//写入存档
+fn save_state(cmd: &arg::Command, game_data: &file::GameData) -> Result<(), WordleError>
+{
+    if let Some(state) = &cmd.state
+    {
+        if let Err(error) = file::write_state(game_data, state)
+        {
+            return Err(WordleError::State{ path: state.clone(), position: None, reason: format!("FAILED TO WRITE: {}", error) });
+        }
     }
+    Ok(())
+}
 
-    //恢复终端
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+//打印统计数据
+fn print_stats(game_data: &file::GameData)
+{
+    let stats = game_data.stats();
+    println!("{} {} {:.2}", stats.success, stats.failed, stats.average);
+    let words: Vec<String> = stats.words
+        .iter()
+        .take(5)
+        .map(|(word, count)| format!("{} {}", word, count))
+        .collect();
+    println!("{}", words.join(" "));
+}
 
+//读取 Y / N，输入结束视为 N
+fn yes_no<I: InputSource>(source: &mut I, prompt: &str) -> Result<bool, WordleError>
+{
+    input::read_yes_no(source, prompt)
 }
-else//用户模式
+
+//未指定词库时使用内置词库
+fn word_list(file: &[String], builtin: &[&str]) -> Vec<String>
 {
-    while again
+    if file.is_empty()
     {
-        game_data.total_rounds += 1;
-        total_round += 1;
-    
-        //生成答案
-        if let Some(_i) = cmd.mode.get("random")
+        builtin.iter().map(|word| word.to_uppercase()).collect()
+    }
+    else
+    {
+        file.to_vec()
+    }
+}
+
+//三类提示：可选词中信息熵最大的、可能的答案中信息熵最大的、全局最优的
+fn hints(matrix: &FeedbackMatrix, session: &GameSession) -> [(&'static str, Vec<(String, f64)>); 3]
+{
+    let (info, help) = solver::help(matrix, session.guesses(), session.results());
+    let all = solver::solve(matrix, session.guesses(), session.results(), session.remaining() as i32 - 1);
+    [
+        ("THE MOST INFORMATIVE GUESSES ARE:", info),
+        ("THE BEST GUESSES ARE:", help),
+        ("THE GLOBAL OPTIMUM GUESSES ARE:", all),
+    ]
+}
+
+//离开TUI后打印的一局记录，未完成的一局不显示答案
+fn summary_line(round: usize, session: &MultiSession) -> String
+{
+    match session.status()
+    {
+        Status::Won => format!("ROUND {}: {} CORRECT {}", round, session.answers().join(" "), session.guesses().len()),
+        Status::Lost => format!("ROUND {}: {} FAILED", round, session.answers().join(" ")),
+        Status::Playing =>
         {
-            match game::random_answer(&mut cmd.answer_file, &mut day, &seed)
-            {
-                Ok(tmp) => answer = tmp,
-                Err(tmp) => return Err(Box::new(MyError{source: tmp}))
-            }
+            let count = session.guesses().len();
+            format!("ROUND {}: UNFINISHED AFTER {} {}", round, count, if count == 1 { "GUESS" } else { "GUESSES" })
         }
-        else
+    }
+}
+
+//一局结束后的分享结果，随机模式下标题中带有日期
+fn share_text(cmd: &arg::Command, session: &MultiSession, day: u64, difficulty: Difficulty) -> String
+{
+    let day = if cmd.random { Some(day) } else { None };
+    share::share_text(session, day, difficulty != Difficulty::Normal, cmd.share.unwrap_or_default(), cmd.theme)
+}
+
+//在TUI侧边面板中显示分享结果，接在统计数据之后
+fn show_share(screen: &mut Screen, share: &str)
+{
+    if screen.side.is_empty()
+    {
+        screen.side_title = "SHARE".to_string();
+    }
+    else
+    {
+        screen.side.push(Spans::from(""));
+    }
+    screen.side.extend(share.lines().map(|line| Spans::from(line.to_string())));
+}
+
+//TUI侧边面板中的提示，每个单词单独一行，颜色取自主题
+fn hint_lines(matrix: &FeedbackMatrix, session: &GameSession, theme: Theme) -> Vec<Spans<'static>>
+{
+    let style = theme.hint().map_or(Style::default(), |color| Style::default().fg(screen::tui_color(color)));
+    let mut lines: Vec<Spans> = Vec::new();
+    for (title, words) in hints(matrix, session)
+    {
+        lines.push(Spans::from(title));
+        for (word, entropy) in words.iter().take(5)
         {
-            if let Some(i) = cmd.info.get("word")
-            {
-                match game::gen_answer(i, &cmd.answer_file)
-                {
-                    Ok(tmp) => answer = tmp,
-                    Err(_tmp) => return Err(Box::new(MyError{source: "INVALID WORD".to_string()})),
-                }
-                again = false;
-            }
-            else
+            lines.push(Spans::from(vec![
+                Span::styled(word.clone(), style),
+                Span::raw(format!(" {:.2}", entropy)),
+            ]));
+        }
+    }
+    lines
+}
+
+//回放：未指定第几局时列出存档中的全部游戏，否则逐次显示该局的猜测
+//测试模式每次猜测输出一行：猜测、各棋盘的结果、各棋盘剩余的答案数、求解器的建议
+fn replay_games(cmd: &arg::Command, game: Option<usize>, game_data: &file::GameData, is_tty: bool) -> Result<(), WordleError>
+{
+    let game = match game
+    {
+        Some(game) => game,
+        None =>
+        {
+            for (index, round) in game_data.games.iter().enumerate()
             {
-                println!("CHOOSE AN ANSWER: ");
-                answer = game::std_answer(&cmd.answer_file);
+                println!("{}", replay_line(index + 1, round, cmd.max_guesses));
             }
+            return Ok(());
         }
-    
-        //记录每局信息
-        let mut record: Vec<String> = Vec::new();
-        let mut this_round: file::Round = file::Round { answer: answer.origin.clone(), guesses: Vec::new() };
-    
-        //开始猜测
-        let mut best_result: HashMap<char, u8> = HashMap::new();
-        let mut is_correct: bool = false;
-        let mut tmp_result: [u8; 5] = [0; 5];
-        let mut full_result: Vec<[u8; 5]> = Vec::new();
-        for i in 1..=6
+    };
+    let round = match game.checked_sub(1).and_then(|index| game_data.games.get(index))
+    {
+        Some(tmp) => tmp,
+        None => return Err(WordleError::bad_argument("GAME", &format!("MUST BE FROM 1 TO {}", game_data.games.len()))),
+    };
+
+    //存档中的这一局无效时直接报错，不必计算结果矩阵
+    replay::new_session(round, &cmd.guess_file, cmd.max_guesses)?;
+    //候选数与建议所用的结果矩阵
+    let custom_matrix = custom_matrix(cmd);
+    let matrix = custom_matrix.as_ref().unwrap_or_else(|| FeedbackMatrix::builtin());
+    let steps = replay::replay(round, &cmd.guess_file, cmd.max_guesses, matrix)?;
+
+    if !is_tty//测试模式
+    {
+        for step in steps.iter()
         {
-            //是否需要提示
-            let mut is_help = false;
-            let mut help = "".to_string();
-            println!("WANT SOME HELP? [Y / N]");
-            match std::io::stdin().read_line(&mut help)
+            let mut line = vec![step.guess().to_string()];
+            for board in step.session.boards()
             {
-                Ok(0) => is_help = false,
-                Err(error) => println!("{}", error),
-                Ok(_) =>
+                match board.results().get(step.session.guesses().len() - 1)
                 {
-                    match help.trim()
-                    {
-                        a if a == "Y" || a == "y" => is_help = true,
-                        b if b == "N" || b == "n" => is_help = false,
-                        _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-                    }
+                    Some(result) => line.push(result.to_string()),
+                    None => line.push("-".to_string()),
                 }
             }
-            if is_help
+            line.extend(step.remaining.iter().map(|count| count.to_string()));
+            line.push(step.suggestion.as_ref().map_or("-".to_string(), |(word, _)| word.clone()));
+            println!("{}", line.join(" "));
+        }
+        return Ok(());
+    }
+
+    let mut stdin = StdinInput::interactive();
+    if yes_no(&mut stdin, "WANT TUI? [Y / N]")?//TUI模式，ENTER 显示下一次猜测，ESC 退出
+    {
+        let guard = TerminalGuard::enter()?;
+        let backend = CrosstermBackend::new(io::stdout());
+        let mut tui = TuiInput::new(Terminal::new(backend)?);
+        tui.screen.animate = !cmd.no_animation;
+        tui.screen.theme = cmd.theme;
+        tui.screen.layout = cmd.dictionary.keyboard.clone();
+        for (index, step) in steps.iter().enumerate()
+        {
+            tui.screen.show(&step.session);
+            tui.screen.side_title = format!("REPLAY GAME {}", game);
+            tui.screen.side = step_lines(index, &steps).into_iter().map(Spans::from).collect();
+            let prompt = if index + 1 < steps.len() { "ENTER: NEXT GUESS  ESC: QUIT" } else { "PRESS ENTER TO QUIT" };
+            if tui.read_line(prompt, 0)?.is_none() && tui.take_action() == Some(Action::Quit)
             {
-                let (info, help) = solver::help(&record, &full_result);
-                print!("THE MOST INFORMATIVE GUESSES ARE:");//可选词中信息熵最大的
-                for i in 0..std::cmp::min(5 as usize, info.len())
-                {
-                    print!(" {}: {:.2}", info[i].0, info[i].1);
-                }
-                println!("");
-                print!("THE BEST GUESSES ARE:");//可能的答案中信息熵最大的
-                for i in 0..std::cmp::min(5 as usize, help.len())
-                {
-                    print!(" {}: {:.2}", help[i].0, help[i].1);
-                }
-                println!("");
-                let all = solver::solve(&record, &full_result, 6 - i);
-                print!("THE GLOBAL OPTIMUM GUESSES ARE:");//全局最优的
-                for i in 0..std::cmp::min(5 as usize, all.len())
-                {
-                    print!(" {}: {:.2}", all[i].0, all[i].1);
-                }
-                println!("");
+                break;
             }
-            println!("CHOOSE A GUESS:");
-            guess = game::std_guess(&cmd.guess_file, &tmp_result, &record, &difficult);
-    
-            //更新一些记录和结果
-            let tmp = guess.origin.clone();
-            record.push(tmp.clone());
-            this_round.guesses.push(tmp.clone());
-            if i != 1 { game_data.games.pop(); }
-            game_data.games.push(this_round.clone());
-            let count = total_word.entry(tmp.clone()).or_insert(0);
-            *count += 1;
-
-            if let Some(state) = cmd.info.get("state")
+        }
+        drop(tui);
+        drop(guard);
+    }
+    else//用户模式，每次按下回车显示下一次猜测
+    {
+        for (index, step) in steps.iter().enumerate()
+        {
+            game::user_show_boards(&step.session, cmd.theme, &cmd.dictionary.keyboard);
+            for line in step_lines(index, &steps)
             {
-                if let Err(_) = file::write_state(&game_data, state)
-                {
-                    return Err(Box::new(MyError{source: "FAILED TO WRITE STATE".to_string()}));
-                }
+                println!("{}", line);
             }
-    
-            tmp_result = answer.compare(&guess.origin);
-            full_result.push(tmp_result);
-            (best_result, is_correct) = game::user_update_and_show(&record, &full_result, best_result);
-            if is_correct
+            if index + 1 < steps.len() && stdin.read_line("PRESS ENTER FOR THE NEXT GUESS", 0)?.is_none()
             {
-                println!("CORRECT {}", i);
-                success_try += i;
-                success_round += 1;
                 break;
             }
         }
-        if !is_correct
+    }
+    Ok(())
+}
+
+//分析存档中的一局，未指定第几局时分析最后一局，--json 时输出 JSON
+fn analyze_game(cmd: &arg::Command, game: Option<usize>, json: bool, game_data: &file::GameData) -> Result<(), WordleError>
+{
+    let game = game.unwrap_or(game_data.games.len());
+    let round = match game.checked_sub(1).and_then(|index| game_data.games.get(index))
+    {
+        Some(tmp) => tmp,
+        None => return Err(WordleError::bad_argument("GAME", &format!("MUST BE FROM 1 TO {}", game_data.games.len()))),
+    };
+    //存档中的这一局无效时直接报错，不必计算结果矩阵
+    replay::new_session(round, &cmd.guess_file, cmd.max_guesses)?;
+    let custom_matrix = custom_matrix(cmd);
+    let matrix = custom_matrix.as_ref().unwrap_or_else(|| FeedbackMatrix::builtin());
+    let analysis = analysis::analyze(round, &cmd.guess_file, cmd.max_guesses, matrix)?;
+    if json
+    {
+        println!("{}", serde_json::to_string_pretty(&analysis).map_err(io::Error::from)?);
+    }
+    else
+    {
+        println!("{}", analysis);
+    }
+    Ok(())
+}
+
+//词库子命令，输出的词库与读入的格式相同，每行一个大写的单词
+fn wordlist_command(command: WordlistCommand) -> Result<(), WordleError>
+{
+    match command
+    {
+        WordlistCommand::Show{ words, acceptable, output } =>
         {
-            println!("FAILED {}", answer.origin);
+            let (_, answer_file, guess_file) = words.load(&words.dictionary()?)?;
+            let list = if acceptable { word_list(&guess_file, ACCEPTABLE) } else { word_list(&answer_file, FINAL) };
+            wordlist::write(output.as_deref(), &list)
         }
-    
-        //打印数据
-        if let Some(_i) = cmd.mode.get("stats")
+        WordlistCommand::Check{ words } => check_word_lists(&words),
+        WordlistCommand::Diff{ first, second, length } =>
         {
-            let mut average: f32 = 0.0;
-            if success_round != 0
+            let first = open_list(&first, length)?;
+            let second = open_list(&second, length)?;
+            for word in wordlist::subtract(&first, &second)
             {
-                average = (success_try as f32) / (success_round as f32);
+                println!("- {}", word);
             }
-            println!("{} {} {:.2}", success_round, total_round - success_round, average);
-            let mut vec: Vec<(&String, &i32)> = total_word.iter().collect();
-            vec.sort_by(|a, b| game::cmp_ref(b, a));
-            let end = if vec.len() < 5 { vec.len() } else { 5 };
-            for i in 0..(end - 1)
+            for word in wordlist::subtract(&second, &first)
             {
-                print!("{} {} ", vec[i].0, vec[i].1);
+                println!("+ {}", word);
             }
-            println!("{} {}", vec[end - 1].0, vec[end - 1].1);
+            Ok(())
         }
-    
-        //是否再来
-        if again
+        WordlistCommand::Merge{ lists, output } =>
         {
-            println!("WANT ANOTHER ROUND? [Y / N]");
-            let mut another = "".to_string();
-            match std::io::stdin().read_line(&mut another)
-            {
-                Ok(0) => again = false,
-                Err(error) => println!("{}", error),
-                Ok(_) =>
-                {
-                    match another.trim()
-                    {
-                        a if a == "Y" || a == "y" => (),
-                        b if b == "N" || b == "n" => again = false,
-                        _ => return Err(Box::new(MyError{source: "INVALID INPUT".to_string()}))
-                    }
-                }
-            }
+            let lists = lists.iter().map(|list| open_list(list, output.length)).collect::<Result<Vec<Vec<String>>, WordleError>>()?;
+            wordlist::write(output.output.as_deref(), &wordlist::merge(&lists))
         }
-    }//while结束
+        WordlistCommand::Subtract{ list, remove, output } =>
+        {
+            let list = open_list(&list, output.length)?;
+            let remove = remove.iter().map(|list| open_list(list, output.length)).collect::<Result<Vec<Vec<String>>, WordleError>>()?;
+            wordlist::write(output.output.as_deref(), &wordlist::subtract(&list, &wordlist::merge(&remove)))
+        }
+        WordlistCommand::Filter{ list, regex, pattern, contains, excludes, output } =>
+        {
+            let filter = wordlist::Filter::new(regex.as_deref(), pattern.as_deref(), contains.as_deref(), excludes.as_deref())?;
+            let words: Vec<String> = open_list(&list, output.length)?.into_iter().filter(|word| filter.matches(word)).collect();
+            wordlist::write(output.output.as_deref(), &words)
+        }
+        WordlistCommand::Stats{ list, length } =>
+        {
+            println!("{}", wordlist::LetterStats::new(&open_list(&list, length)?));
+            Ok(())
+        }
+    }
 }
+
+//读取词库文件或内置词库，有不合法的行时返回错误
+fn open_list(name: &str, length: Option<usize>) -> Result<Vec<String>, WordleError>
+{
+    let list = WordList::open(name, length)?;
+    list.validate()?;
+    Ok(list.words)
 }
 
-    //存档
-    if let Some(state) = cmd.info.get("state")
+//检查词库：列出每个词库中有问题的行与不在候选词库中的答案，词库不可用时返回错误
+fn check_word_lists(words: &arg::WordListArgs) -> Result<(), WordleError>
+{
+    let dictionary = words.dictionary()?;
+    let length = words.length(&dictionary)?;
+    let (final_set, acceptable_set) = words.sets(&dictionary);
+    let read = |file: &Option<String>, builtin: &[&str]| match file
+    {
+        Some(file) => WordList::read_in(file, Some(length), &dictionary.alphabet),
+        None => Ok(WordList::builtin("builtin", builtin)),
+    };
+    let answers = read(&final_set, FINAL)?;
+    let guesses = read(&acceptable_set, ACCEPTABLE)?;
+    for (title, list) in [("FINAL", &answers), ("ACCEPTABLE", &guesses)]
     {
-        if let Err(_) = file::write_state(&game_data, state)
+        println!("{} {}: {} WORDS", title, list.path, list.words.len());
+        for issue in list.issues.iter()
         {
-            return Err(Box::new(MyError{source: "FAILED TO WRITE STATE".to_string()}));
+            println!("{}", issue);
         }
     }
+    let missing = wordlist::subtract(&answers.words, &guesses.words);
+    println!("MISSING FROM ACCEPTABLE: {}", missing.len());
+    for word in missing.iter()
+    {
+        println!("{}", word);
+    }
 
+    answers.validate()?;
+    guesses.validate()?;
+    if !missing.is_empty()
+    {
+        return Err(WordleError::WordList{ path: answers.path, line: None, reason: format!("ANSWERS MISSING FROM {}: {}", guesses.path, missing.len()) });
+    }
+    println!("OK");
     Ok(())
 }
 
-//测试全局最优算法
-#[cfg(feature = "calculate")]
-fn main()
+//词典子命令：列出全部词典，或打印一个词典的字母表、键盘布局与词库
+fn dictionary_command(command: DictionaryCommand) -> Result<(), WordleError>
 {
-    solver::test();
+    match command
+    {
+        DictionaryCommand::List =>
+        {
+            for name in dictionary::names()
+            {
+                match Dictionary::open(&name)
+                {
+                    Ok(dictionary) if dictionary.description.is_empty() => println!("{}", name),
+                    Ok(dictionary) => println!("{}: {}", name, dictionary.description),
+                    Err(error) => println!("{}: {}", name, error),
+                }
+            }
+            Ok(())
+        }
+        DictionaryCommand::Show{ name } =>
+        {
+            println!("{}", Dictionary::open(&name)?);
+            Ok(())
+        }
+    }
+}
+
+//使用自定义词库时的结果矩阵，内置词库返回 None
+fn custom_matrix(cmd: &arg::Command) -> Option<FeedbackMatrix>
+{
+    if cmd.guess_file.is_empty() && cmd.answer_file.is_empty()
+    {
+        None
+    }
+    else
+    {
+        Some(FeedbackMatrix::cached(&word_list(&cmd.guess_file, ACCEPTABLE), &word_list(&cmd.answer_file, FINAL)))
+    }
 }
 
-//求解器
-#[cfg(feature = "solver")]
-fn main()
+//存档中一局的概要，未完成的一局不显示答案
+fn replay_line(game: usize, round: &file::Round, max_guesses: usize) -> String
 {
+    let count = round.guesses.len();
+    if round.is_success()
+    {
+        format!("{}: {} CORRECT {}", game, round.all_answers().join(" "), count)
+    }
+    else if count >= session::total_guesses(round.all_answers().len(), max_guesses)
+    {
+        format!("{}: {} FAILED {}", game, round.all_answers().join(" "), count)
+    }
+    else
+    {
+        format!("{}: UNFINISHED {}", game, count)
+    }
+}
+
+//回放中一步的说明：第几次猜测、剩余的答案数与求解器的建议
+fn step_lines(index: usize, steps: &[ReplayStep]) -> Vec<String>
+{
+    let step = &steps[index];
+    let remaining: Vec<String> = step.remaining.iter().map(|count| count.to_string()).collect();
+    let mut lines = vec![
+        format!("GUESS {} OF {}: {}", index + 1, steps.len(), step.guess()),
+        format!("REMAINING ANSWERS: {}", remaining.join(" ")),
+    ];
+    if let Some((word, entropy)) = &step.suggestion
+    {
+        lines.push(format!("SOLVER SUGGESTED: {} {:.2}", word, entropy));
+    }
+    lines
+}
+
+//求解器：输入在别处进行的游戏中的猜测与结果，按 c 计算建议
+//可能的答案与单词长度来自选用的词典与词库
+fn solve(cmd: &arg::Command)
+{
+    let custom_matrix = custom_matrix(cmd);
+    let matrix = custom_matrix.as_ref().unwrap_or_else(|| FeedbackMatrix::builtin());
     let mut again: bool = true;
     while again
     {
         let mut record: Vec<String> = Vec::new();
-        let mut full_result: Vec<[u8; 5]> = Vec::new();
+        let mut full_result: Vec<Pattern> = Vec::new();
         loop
         {
             println!("n for new game, c for compute, q for quit");
@@ -687,60 +953,47 @@ fn main()
                 }
                 "c" =>
                 {
-                    let (info, help) = solver::help(&record, &full_result);
+                    let (info, help) = solver::help(matrix, &record, &full_result);
                     print!("THE MOST INFORMATIVE GUESSES ARE:");//可选词中信息熵最大的
-                    for i in 0..std::cmp::min(5 as usize, info.len())
+                    for (word, entropy) in info.iter().take(5)
                     {
-                        print!(" {}: {:.2}", info[i].0, info[i].1);
+                        print!(" {}: {:.2}", word, entropy);
                     }
-                    println!("");
+                    println!();
                     print!("THE BEST GUESSES ARE:");//可能的答案中信息熵最大的
-                    for i in 0..std::cmp::min(5 as usize, help.len())
+                    for (word, entropy) in help.iter().take(5)
                     {
-                        print!(" {}: {:.2}", help[i].0, help[i].1);
+                        print!(" {}: {:.2}", word, entropy);
                     }
-                    println!("");
-                    let all = solver::solve(&record, &full_result, 6);
+                    println!();
+                    let all = solver::solve(matrix, &record, &full_result, cmd.max_guesses as i32);
                     print!("THE GLOBAL OPTIMUM GUESSES ARE:");//全局最优的
-                    for i in 0..std::cmp::min(5 as usize, all.len())
+                    for (word, entropy) in all.iter().take(5)
                     {
-                        print!(" {}: {:.2}", all[i].0, all[i].1);
+                        print!(" {}: {:.2}", word, entropy);
                     }
-                        println!("");
+                    println!();
                     continue;
                 }
                 _ => (),
             }
-            if word.len() != 5
+            if word.chars().count() != cmd.length || !word.chars().all(|letter| dictionary::is_letter(&cmd.dictionary.alphabet, letter))
             {
                 println!("invalid word");
                 continue;
             }
             let str_result: String = read!();
-            if str_result.len() != 5
-            {
-                println!("invalid result");
-                continue;
-            }
-            let mut u8_result: [u8; 5] = [0; 5];
-            let mut count: usize = 0;
-            for i in str_result.chars()
+            let result = match str_result.parse::<Pattern>()
             {
-                match i
+                Ok(tmp) if tmp.length() == word.chars().count() => tmp,
+                _ =>
                 {
-                    'R' => u8_result[count] = 1,
-                    'Y' => u8_result[count] = 2,
-                    'G' => u8_result[count] = 3,
-                    _ =>
-                    {
-                        println!("invalid result");
-                        continue;
-                    }
+                    println!("invalid result");
+                    continue;
                 }
-                count += 1;
-            }
-            record.push(word.to_uppercase());
-            full_result.push(u8_result);
+            };
+            record.push(dictionary::uppercase(&word));
+            full_result.push(result);
         }
     }
-}
\ No newline at end of file
+}
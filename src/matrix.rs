@@ -0,0 +1,273 @@
+use crate::builtin_words::{ACCEPTABLE, FINAL};
+use crate::feedback::{Pattern, DEFAULT_LENGTH};
+use crate::game::Word;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+//缓存文件格式：魔数、版本、词库哈希、行列数、单词长度，之后按行存放每个结果的编码（u16 小端）
+const MAGIC: &[u8; 4] = b"WDLM";
+const VERSION: u32 = 2;
+
+//所有 可选词 × 答案词 的比较结果，行为猜测，列为答案
+pub struct FeedbackMatrix
+{
+    guesses: Vec<String>,
+    answers: Vec<String>,
+    guess_index: HashMap<String, usize>,
+    answer_index: HashMap<String, usize>,
+    length: usize,
+    data: Vec<u16>,
+}
+
+impl FeedbackMatrix
+{
+    //并行计算全部结果
+    pub fn new(guesses: &[String], answers: &[String]) -> FeedbackMatrix
+    {
+        let words: Vec<Word> = answers.iter().map(|answer| Word::new(answer)).collect();
+        let data: Vec<u16> = guesses
+            .par_iter()
+            .flat_map_iter(|guess|
+            {
+                let guess = Word::new(guess);
+                words.iter().map(move |answer| answer.compare_word(&guess).code())
+            })
+            .collect();
+        FeedbackMatrix::from_parts(guesses, answers, data)
+    }
+
+    fn from_parts(guesses: &[String], answers: &[String], data: Vec<u16>) -> FeedbackMatrix
+    {
+        FeedbackMatrix
+        {
+            guesses: guesses.to_vec(),
+            answers: answers.to_vec(),
+            guess_index: guesses.iter().enumerate().map(|(i, word)| (word.clone(), i)).collect(),
+            answer_index: answers.iter().enumerate().map(|(i, word)| (word.clone(), i)).collect(),
+            length: word_length(answers),
+            data,
+        }
+    }
+
+    //内置词库对应的矩阵，只计算一次，并尽量使用磁盘缓存
+    pub fn builtin() -> &'static FeedbackMatrix
+    {
+        static BUILTIN: OnceLock<FeedbackMatrix> = OnceLock::new();
+        BUILTIN.get_or_init(||
+        {
+            let guesses: Vec<String> = ACCEPTABLE.iter().map(|word| word.to_uppercase()).collect();
+            let answers: Vec<String> = FINAL.iter().map(|word| word.to_uppercase()).collect();
+            FeedbackMatrix::cached(&guesses, &answers)
+        })
+    }
+
+    //任意词库对应的矩阵，使用默认的缓存目录
+    pub fn cached(guesses: &[String], answers: &[String]) -> FeedbackMatrix
+    {
+        let path = cache_dir().map(|dir| dir.join(cache_name(guesses, answers)));
+        FeedbackMatrix::load_or_build(guesses, answers, path.as_deref())
+    }
+
+    //缓存有效时直接读取，否则重新计算并写回缓存
+    pub fn load_or_build(guesses: &[String], answers: &[String], path: Option<&Path>) -> FeedbackMatrix
+    {
+        if let Some(path) = path
+        {
+            if let Ok(matrix) = FeedbackMatrix::load(path, guesses, answers)
+            {
+                return matrix;
+            }
+        }
+        let matrix = FeedbackMatrix::new(guesses, answers);
+        if let Some(path) = path
+        {
+            let _ = matrix.save(path);
+        }
+        matrix
+    }
+
+    //读取缓存，词库哈希或大小不一致时报错
+    pub fn load(path: &Path, guesses: &[String], answers: &[String]) -> io::Result<FeedbackMatrix>
+    {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = [0u8; 28];
+        reader.read_exact(&mut header)?;
+        let invalid = |msg: &str| io::Error::new(ErrorKind::InvalidData, msg.to_string());
+        if &header[0..4] != MAGIC
+        {
+            return Err(invalid("NOT A FEEDBACK MATRIX"));
+        }
+        if u32::from_le_bytes(header[4..8].try_into().unwrap()) != VERSION
+        {
+            return Err(invalid("UNSUPPORTED VERSION"));
+        }
+        if u64::from_le_bytes(header[8..16].try_into().unwrap()) != word_list_hash(guesses, answers)
+        {
+            return Err(invalid("WORD LIST MISMATCH"));
+        }
+        let rows = u32::from_le_bytes(header[16..20].try_into().unwrap()) as usize;
+        let cols = u32::from_le_bytes(header[20..24].try_into().unwrap()) as usize;
+        let length = u32::from_le_bytes(header[24..28].try_into().unwrap()) as usize;
+        if rows != guesses.len() || cols != answers.len() || length != word_length(answers)
+        {
+            return Err(invalid("SIZE MISMATCH"));
+        }
+        let mut bytes = vec![0u8; rows * cols * 2];
+        reader.read_exact(&mut bytes)?;
+        let count = Pattern::count(length);
+        let data = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .map(|code| if (code as usize) < count { Ok(code) } else { Err(invalid("INVALID PATTERN")) })
+            .collect::<io::Result<Vec<u16>>>()?;
+        Ok(FeedbackMatrix::from_parts(guesses, answers, data))
+    }
+
+    //写入缓存，先写临时文件再改名，避免留下不完整的文件
+    pub fn save(&self, path: &Path) -> io::Result<()>
+    {
+        if let Some(dir) = path.parent()
+        {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            writer.write_all(MAGIC)?;
+            writer.write_all(&VERSION.to_le_bytes())?;
+            writer.write_all(&word_list_hash(&self.guesses, &self.answers).to_le_bytes())?;
+            writer.write_all(&(self.guesses.len() as u32).to_le_bytes())?;
+            writer.write_all(&(self.answers.len() as u32).to_le_bytes())?;
+            writer.write_all(&(self.length as u32).to_le_bytes())?;
+            let bytes: Vec<u8> = self.data.iter().flat_map(|code| code.to_le_bytes()).collect();
+            writer.write_all(&bytes)?;
+            writer.flush()?;
+        }
+        fs::rename(tmp, path)
+    }
+
+    pub fn guesses(&self) -> &[String]
+    {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> &[String]
+    {
+        &self.answers
+    }
+
+    //单词长度与不同结果的总数
+    pub fn length(&self) -> usize
+    {
+        self.length
+    }
+
+    pub fn pattern_count(&self) -> usize
+    {
+        Pattern::count(self.length)
+    }
+
+    pub fn guess_index(&self, word: &str) -> Option<usize>
+    {
+        self.guess_index.get(word).copied()
+    }
+
+    pub fn answer_index(&self, word: &str) -> Option<usize>
+    {
+        self.answer_index.get(word).copied()
+    }
+
+    //按下标查询结果
+    pub fn get(&self, guess: usize, answer: usize) -> Pattern
+    {
+        let code = self.data[guess * self.answers.len() + answer];
+        Pattern::from_code(code, self.length).unwrap()
+    }
+
+    //某个猜测对所有答案结果的编码
+    pub fn row(&self, guess: usize) -> &[u16]
+    {
+        let cols = self.answers.len();
+        &self.data[guess * cols..(guess + 1) * cols]
+    }
+
+    //按单词查询，不在矩阵中的猜测现场计算
+    pub fn pattern(&self, guess: &str, answer: usize) -> Pattern
+    {
+        match self.guess_index(guess)
+        {
+            Some(index) => self.get(index, answer),
+            None => Word::new(&self.answers[answer]).compare(guess),
+        }
+    }
+
+    //与历史猜测及其结果都相符的答案下标
+    pub fn candidates(&self, record: &[String], full_result: &[Pattern]) -> Vec<usize>
+    {
+        (0..self.answers.len())
+            .filter(|&answer|
+            {
+                record
+                    .iter()
+                    .zip(full_result.iter())
+                    .all(|(guess, result)| self.pattern(guess, answer) == *result)
+            })
+            .collect()
+    }
+}
+
+//词库中单词的长度，空词库按默认长度处理
+fn word_length(answers: &[String]) -> usize
+{
+    answers.first().map_or(DEFAULT_LENGTH, |word| word.chars().count())
+}
+
+//缓存目录：WORDLE_CACHE_DIR，否则为 XDG_CACHE_HOME/wordle 或 ~/.cache/wordle
+pub fn cache_dir() -> Option<PathBuf>
+{
+    if let Some(dir) = std::env::var_os("WORDLE_CACHE_DIR")
+    {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME")
+    {
+        return Some(PathBuf::from(dir).join("wordle"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache").join("wordle"))
+}
+
+//缓存文件名包含版本与词库哈希，词库变化时自动失效
+pub fn cache_name(guesses: &[String], answers: &[String]) -> String
+{
+    format!("matrix-v{}-{:016x}.bin", VERSION, word_list_hash(guesses, answers))
+}
+
+//FNV-1a 哈希，结果不随编译器版本变化
+pub fn word_list_hash(guesses: &[String], answers: &[String]) -> u64
+{
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |bytes: &[u8]|
+    {
+        for byte in bytes
+        {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    for word in guesses
+    {
+        feed(word.as_bytes());
+        feed(b"\n");
+    }
+    feed(b"\0");
+    for word in answers
+    {
+        feed(word.as_bytes());
+        feed(b"\n");
+    }
+    hash
+}
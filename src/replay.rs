@@ -0,0 +1,60 @@
+use crate::error::WordleError;
+use crate::file::Round;
+use crate::game::Word;
+use crate::hard_mode::Difficulty;
+use crate::matrix::FeedbackMatrix;
+use crate::session::MultiSession;
+use crate::solver;
+
+//回放中的一步：提交该次猜测后的整局状态、各棋盘仍然可能的答案数、猜测前求解器的建议
+#[derive(Debug, Clone)]
+pub struct ReplayStep
+{
+    pub session: MultiSession,
+    pub remaining: Vec<usize>,
+    pub suggestion: Option<(String, f64)>,
+}
+
+impl ReplayStep
+{
+    //本步的猜测
+    pub fn guess(&self) -> &str
+    {
+        self.session.guesses().last().map_or("", |guess| guess.as_str())
+    }
+}
+
+//按存档中的答案重新计算每次猜测的结果，答案尚未确定的一局无法回放
+//候选数与建议来自 matrix，求解器的建议为猜测前可能答案中信息熵最大的单词
+pub fn replay(round: &Round, guess_file: &[String], max_guesses: usize, matrix: &FeedbackMatrix) -> Result<Vec<ReplayStep>, WordleError>
+{
+    let mut session = new_session(round, guess_file, max_guesses)?;
+    let mut steps = Vec::new();
+    for guess in round.guesses.iter()
+    {
+        let suggestion = session.current_board().and_then(|board|
+        {
+            let (info, help) = solver::help(matrix, board.guesses(), board.results());
+            help.into_iter().next().or_else(|| info.into_iter().next())
+        });
+        session.submit_guess(guess)?;
+        let remaining = session
+            .boards()
+            .iter()
+            .map(|board| matrix.candidates(board.guesses(), board.results()).len())
+            .collect();
+        steps.push(ReplayStep{ session: session.clone(), remaining, suggestion });
+    }
+    Ok(steps)
+}
+
+//以存档中的答案开始一局，尚未猜测，答案尚未确定时返回错误
+pub fn new_session(round: &Round, guess_file: &[String], max_guesses: usize) -> Result<MultiSession, WordleError>
+{
+    if round.all_answers().iter().any(|answer| answer.is_empty())
+    {
+        return Err(WordleError::bad_argument("GAME", "UNFINISHED GAME"));
+    }
+    let answers = round.all_answers().iter().map(|answer| Word::new(answer)).collect();
+    Ok(MultiSession::new(answers, guess_file, Difficulty::Normal, max_guesses))
+}
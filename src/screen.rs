@@ -0,0 +1,760 @@
+use std::collections::HashMap;
+use std::io;
+use std::panic;
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+use crossterm::cursor::Show;
+use crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use tui::backend::Backend;
+use tui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use tui::style::{Color, Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::{Block, Borders, Paragraph, Wrap};
+use tui::{Frame, Terminal};
+use crate::feedback::{LetterFeedback, Pattern};
+use crate::dictionary::{self, QWERTY};
+use crate::game::BOARDS_PER_ROW;
+use crate::session::{MultiSession, Status};
+use crate::theme::Theme;
+
+//每个格子的宽和高（含边框）
+const TILE_WIDTH: u16 = 5;
+const TILE_HEIGHT: u16 = 3;
+//键盘上每个按键的宽度，以及棋盘之间的间隔
+const KEY_WIDTH: u16 = 3;
+const GAP: u16 = 2;
+//宽屏时提示面板的宽度，窄屏时提示面板位于棋盘下方的高度
+const SIDE_WIDTH: u16 = 36;
+const SIDE_HEIGHT: u16 = 8;
+//键盘与状态栏的高度
+const KEYBOARD_HEIGHT: u16 = 5;
+const STATUS_HEIGHT: u16 = 4;
+
+//动画：每个格子翻转的时间、不合法输入的抖动时间、猜中后每个格子跳起的间隔，以及刷新间隔
+const FLIP: Duration = Duration::from_millis(250);
+const SHAKE: Duration = Duration::from_millis(400);
+const BOUNCE: Duration = Duration::from_millis(100);
+const TICK: Duration = Duration::from_millis(30);
+
+//TUI中除输入文字以外的按键操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action
+{
+    Hint,
+    NewGame,
+    Share,
+    Quit,
+}
+
+//进入TUI时开启原始模式与备用屏幕，离开作用域时恢复终端
+//panic 时先恢复终端再打印信息，否则信息会留在备用屏幕中
+//panic hook 只安装一次，只在有 guard 时恢复终端，guard 离开后不再生效
+pub struct TerminalGuard;
+
+//当前 guard 的恢复函数，恢复后清空，保证只恢复一次
+static ACTIVE: Mutex<Option<fn()>> = Mutex::new(None);
+static HOOK: Once = Once::new();
+
+impl TerminalGuard
+{
+    pub fn enter() -> io::Result<TerminalGuard>
+    {
+        TerminalGuard::with(enter_terminal, restore_terminal)
+    }
+
+    //使用给定的进入与恢复方式，测试中不操作真实的终端
+    pub fn with(enter: fn() -> io::Result<()>, restore: fn()) -> io::Result<TerminalGuard>
+    {
+        HOOK.call_once(||
+        {
+            let hook = panic::take_hook();
+            panic::set_hook(Box::new(move |info|
+            {
+                if let Some(restore) = take_active()
+                {
+                    restore();
+                }
+                hook(info);
+            }));
+        });
+        *ACTIVE.lock().unwrap_or_else(|error| error.into_inner()) = Some(restore);
+        let guard = TerminalGuard;
+        enter()?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard
+{
+    fn drop(&mut self)
+    {
+        if let Some(restore) = take_active()
+        {
+            restore();
+        }
+    }
+}
+
+fn take_active() -> Option<fn()>
+{
+    ACTIVE.lock().unwrap_or_else(|error| error.into_inner()).take()
+}
+
+fn enter_terminal() -> io::Result<()>
+{
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+}
+
+fn restore_terminal()
+{
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+//单个棋盘的显示内容
+struct BoardView
+{
+    guesses: Vec<String>,
+    results: Vec<Pattern>,
+    status: Status,
+}
+
+impl BoardView
+{
+    fn over(&self) -> bool
+    {
+        self.status != Status::Playing
+    }
+}
+
+//动画效果：翻开 boards 中各棋盘最新的一行，其中 won 中的棋盘随后跳起；或抖动被拒绝的输入
+enum Effect
+{
+    Reveal{ boards: Vec<usize>, won: Vec<usize> },
+    Shake(String),
+}
+
+//elapsed 为已播放的时间，由 Screen::advance 推进，不读取系统时钟
+struct Animation
+{
+    effect: Effect,
+    elapsed: Duration,
+}
+
+impl Animation
+{
+    fn new(effect: Effect) -> Animation
+    {
+        Animation{ effect, elapsed: Duration::ZERO }
+    }
+
+    fn duration(&self, length: usize) -> Duration
+    {
+        match &self.effect
+        {
+            Effect::Reveal{ won, .. } if !won.is_empty() => FLIP * length as u32 + BOUNCE * (length as u32 + 1),
+            Effect::Reveal{ .. } => FLIP * length as u32,
+            Effect::Shake(_) => SHAKE,
+        }
+    }
+}
+
+//TUI的全部显示内容：棋盘格子、键盘、状态栏与侧边面板
+#[derive(Default)]
+pub struct Screen
+{
+    boards: Vec<BoardView>,
+    rows: usize,
+    length: usize,
+    keyboard: HashMap<char, LetterFeedback>,
+    pub prompt: String,
+    pub message: String,
+    pub side_title: String,
+    pub side: Vec<Spans<'static>>,
+    pub animate: bool,
+    pub theme: Theme,
+    //词典的键盘布局，为空时使用 QWERTY
+    pub layout: Vec<String>,
+    animation: Option<Animation>,
+    scroll: usize,
+}
+
+impl Screen
+{
+    pub fn new() -> Screen
+    {
+        Screen::default()
+    }
+
+    //开始新的一局前清空所有内容，保留动画、主题与键盘布局的设置
+    pub fn clear(&mut self)
+    {
+        *self = Screen{ animate: self.animate, theme: self.theme, layout: std::mem::take(&mut self.layout), ..Screen::new() };
+    }
+
+    //从游戏状态更新棋盘与键盘，并清除上一条消息
+    //键盘显示当前棋盘的字母状态，全部结束后显示最后一个棋盘
+    //同一局中新增的猜测会被逐个翻开，猜中的棋盘随后跳起
+    pub fn show(&mut self, session: &MultiSession)
+    {
+        let previous: Vec<usize> = self.boards.iter().map(|board| board.guesses.len()).collect();
+        self.boards = session.boards().iter().map(|board| BoardView
+            {
+                guesses: board.guesses().to_vec(),
+                results: board.results().to_vec(),
+                status: board.status(),
+            })
+            .collect();
+        self.scroll = 0;
+        if self.animate && previous.len() == self.boards.len()
+        {
+            let boards: Vec<usize> = (0..self.boards.len())
+                .filter(|&index| self.boards[index].guesses.len() == previous[index] + 1)
+                .collect();
+            let won = boards.iter().copied().filter(|&index| self.boards[index].status == Status::Won).collect();
+            if !boards.is_empty()
+            {
+                self.animation = Some(Animation::new(Effect::Reveal{ boards, won }));
+            }
+        }
+        self.rows = session.boards().first().map_or(0, |board| board.max_guesses());
+        self.length = session.length();
+        self.keyboard = session
+            .current_board()
+            .or(session.boards().last())
+            .map_or_else(HashMap::new, |board| board.keyboard().clone());
+        self.message.clear();
+    }
+
+    //拒绝一次输入：正在猜测时抖动该输入，不合法的单词不再另外显示消息
+    pub fn reject(&mut self, input: &str, message: &str)
+    {
+        if self.animate && self.typing()
+        {
+            self.animation = Some(Animation::new(Effect::Shake(input.trim().to_string())));
+            if message == "INVALID"
+            {
+                self.message.clear();
+                return;
+            }
+        }
+        self.message = message.to_string();
+    }
+
+    //是否有正在播放的动画，播放期间需要定时刷新
+    pub fn animating(&self) -> bool
+    {
+        self.animation.as_ref().is_some_and(|animation| animation.elapsed < animation.duration(self.length))
+    }
+
+    //动画向前播放 elapsed 的时间
+    pub fn advance(&mut self, elapsed: Duration)
+    {
+        if let Some(animation) = self.animation.as_mut()
+        {
+            animation.elapsed += elapsed;
+        }
+    }
+
+    //是否有正在进行的棋盘，此时输入显示在棋盘的下一行中
+    fn typing(&self) -> bool
+    {
+        self.boards.iter().any(|board| !board.over())
+    }
+
+    //单个棋盘的宽度
+    fn board_width(&self) -> u16
+    {
+        self.length as u16 * TILE_WIDTH
+    }
+
+    //全部棋盘排成一行（最多 BOARDS_PER_ROW 个）所需的宽度
+    fn boards_width(&self) -> u16
+    {
+        let columns = self.boards.len().clamp(1, BOARDS_PER_ROW) as u16;
+        (self.board_width() + GAP) * columns - GAP
+    }
+
+    //界面所需的最小宽度与高度：放得下一个棋盘的一行与整个键盘
+    fn min_size(&self) -> (u16, u16)
+    {
+        let width = self.keyboard_width().max(self.board_width()) + 4;
+        let height = TILE_HEIGHT + 2 + KEYBOARD_HEIGHT + STATUS_HEIGHT + 2;
+        (width, height)
+    }
+
+    //键盘上每个按键所在的位置
+    fn keys(&self, area: Rect) -> Vec<(KeyCode, Rect)>
+    {
+        match self.layout.is_empty()
+        {
+            true => layout_keys(area, &QWERTY),
+            false => layout_keys(area, &self.layout),
+        }
+    }
+
+    //键盘最长一行的宽度
+    fn keyboard_width(&self) -> u16
+    {
+        self.keys(Rect::new(0, 0, u16::MAX / 2, KEYBOARD_HEIGHT)).iter().map(|(_, key)| key.right()).max().unwrap_or(0)
+    }
+
+    fn too_small(&self, size: Rect) -> bool
+    {
+        let (width, height) = self.min_size();
+        size.width < width || size.height < height
+    }
+
+    //界面各部分所在的区域：宽屏时提示面板在棋盘右侧，窄屏时在棋盘下方且只在有内容时显示
+    fn areas(&self, size: Rect) -> Areas
+    {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints(
+                [
+                    Constraint::Min(TILE_HEIGHT + 2),
+                    Constraint::Length(KEYBOARD_HEIGHT),
+                    Constraint::Length(STATUS_HEIGHT),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+        let (boards, side) = if chunks[0].width >= self.boards_width() + 2 + SIDE_WIDTH
+        {
+            let top = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Min(TILE_WIDTH + 2), Constraint::Length(SIDE_WIDTH)].as_ref())
+                .split(chunks[0]);
+            (top[0], top[1])
+        }
+        else if self.side.is_empty() || chunks[0].height < TILE_HEIGHT + 2 + SIDE_HEIGHT
+        {
+            (chunks[0], Rect::default())
+        }
+        else
+        {
+            let top = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(TILE_HEIGHT + 2), Constraint::Length(SIDE_HEIGHT)].as_ref())
+                .split(chunks[0]);
+            (top[0], top[1])
+        };
+        Areas{ boards, side, keyboard: chunks[1], status: chunks[2] }
+    }
+
+    //棋盘在区域中的排列：每行的棋盘数、每个棋盘可见的行数与第一行
+    //放不下全部猜测时显示当前一行附近，scroll 为向上翻过的行数
+    fn grid(&self, area: Rect) -> Grid
+    {
+        let columns = (((area.width + GAP) / (self.board_width() + GAP).max(1)) as usize).clamp(1, BOARDS_PER_ROW);
+        let lines = self.boards.len().div_ceil(columns).max(1) as u16;
+        let visible = ((((area.height + 1) / lines).saturating_sub(1) / TILE_HEIGHT) as usize).clamp(1, self.rows.max(1));
+        let current = self.boards.iter().map(|board| board.guesses.len()).max().unwrap_or(0).min(self.rows.saturating_sub(1));
+        let latest = (current + 1).saturating_sub(visible);
+        Grid{ columns, visible, first: latest - self.scroll.min(latest) }
+    }
+
+    //终端中 (column, row) 处的键盘按键
+    pub fn key_at(&self, size: Rect, column: u16, row: u16) -> Option<KeyCode>
+    {
+        if self.too_small(size)
+        {
+            return None;
+        }
+        let keyboard = Block::default().borders(Borders::ALL).inner(self.areas(size).keyboard);
+        self.keys(keyboard)
+            .into_iter()
+            .find(|(_, key)| key.x <= column && column < key.right() && key.y <= row && row < key.bottom())
+            .map(|(code, _)| code)
+    }
+
+    //绘制整个界面，input 为正在输入的内容，终端过小时只显示提示
+    pub fn draw<B: Backend>(&self, f: &mut Frame<B>, input: &str)
+    {
+        let size = f.size();
+        if self.too_small(size)
+        {
+            let (width, height) = self.min_size();
+            let text = vec![
+                Spans::from("TERMINAL TOO SMALL"),
+                Spans::from(format!("NEED {}x{}, HAVE {}x{}", width, height, size.width, size.height)),
+            ];
+            f.render_widget(Paragraph::new(text).alignment(Alignment::Center).wrap(Wrap{ trim: true }), size);
+            return;
+        }
+        let areas = self.areas(size);
+        self.draw_boards(f, areas.boards, input);
+        if areas.side.area() > 0
+        {
+            self.draw_side(f, areas.side);
+        }
+        self.draw_keyboard(f, areas.keyboard);
+        self.draw_status(f, areas.status, input);
+    }
+
+    //棋盘：每个字母一个带边框的格子，背景色为比较结果
+    //区域放不下全部棋盘时不播放动画
+    fn draw_boards<B: Backend>(&self, f: &mut Frame<B>, area: Rect, input: &str)
+    {
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let grid = self.grid(inner);
+        let title = if grid.visible < self.rows
+        {
+            format!("WORDLE  ROWS {}-{} OF {}  PGUP/PGDN", grid.first + 1, grid.first + grid.visible, self.rows)
+        }
+        else
+        {
+            "WORDLE".to_string()
+        };
+        f.render_widget(Block::default().borders(Borders::ALL).title(title), area);
+
+        let animation = self.animation.as_ref().filter(|_| grid.visible == self.rows && grid.columns >= self.boards.len().min(BOARDS_PER_ROW));
+        let elapsed = animation.map_or(Duration::ZERO, |animation| animation.elapsed);
+        let board_height = grid.visible as u16 * TILE_HEIGHT;
+        for (index, board) in self.boards.iter().enumerate()
+        {
+            let x = inner.x + (index % grid.columns) as u16 * (self.board_width() + GAP);
+            let y = inner.y + (index / grid.columns) as u16 * (board_height + 1);
+            for row in grid.first..grid.first + grid.visible
+            {
+                for col in 0..self.length
+                {
+                    let top = y + (row - grid.first) as u16 * TILE_HEIGHT;
+                    let mut tile = Rect::new(x + col as u16 * TILE_WIDTH, top, TILE_WIDTH, TILE_HEIGHT);
+                    if !fits(tile, inner)
+                    {
+                        continue;
+                    }
+                    let (mut letter, mut color) = match (board.guesses.get(row), board.results.get(row))
+                    {
+                        (Some(guess), Some(result)) => (guess.chars().nth(col), Some(result.get(col))),
+                        _ if !board.over() && row == board.guesses.len() => (input.chars().nth(col), None),
+                        _ => (None, None),
+                    };
+                    let mut flat = false;
+                    match animation.map(|animation| &animation.effect)
+                    {
+                        //每个格子依次翻转：先压扁，翻到背面后显示颜色，再恢复原来的高度
+                        Some(Effect::Reveal{ boards, won }) if boards.contains(&index) && row + 1 == board.guesses.len() =>
+                        {
+                            let phase = elapsed.as_secs_f64() / FLIP.as_secs_f64() - col as f64;
+                            if phase < 0.5
+                            {
+                                color = None;
+                            }
+                            flat = (0.25..0.75).contains(&phase);
+                            let bounce = elapsed.saturating_sub(FLIP * self.length as u32);
+                            let step = (bounce.as_millis() / BOUNCE.as_millis()) as usize;
+                            if won.contains(&index) && !bounce.is_zero() && (col..col + 2).contains(&step) && tile.y > inner.y
+                            {
+                                tile.y -= 1;
+                            }
+                        }
+                        //被拒绝的输入左右抖动，开始输入新的单词后停止
+                        Some(Effect::Shake(word)) if input.is_empty() && !board.over() && row == board.guesses.len() =>
+                        {
+                            letter = word.chars().nth(col);
+                            let frame = elapsed.as_millis() / TICK.as_millis();
+                            let shaken = if frame % 2 == 0 { Rect{ x: tile.x + 1, ..tile } } else { Rect{ x: tile.x.saturating_sub(1), ..tile } };
+                            if fits(shaken, inner)
+                            {
+                                tile = shaken;
+                            }
+                        }
+                        _ => (),
+                    }
+                    if flat
+                    {
+                        let line = Rect{ y: tile.y + TILE_HEIGHT / 2, height: 1, ..tile };
+                        f.render_widget(Paragraph::new(tile_text(self.theme, letter, color))
+                            .alignment(Alignment::Center)
+                            .style(tile_style(self.theme, letter, color)), line);
+                    }
+                    else
+                    {
+                        f.render_widget(tile_widget(self.theme, letter, color), tile);
+                    }
+                }
+            }
+        }
+    }
+
+    //侧边面板：提示或统计数据，默认显示按键说明
+    fn draw_side<B: Backend>(&self, f: &mut Frame<B>, area: Rect)
+    {
+        let (title, text) = if self.side.is_empty()
+        {
+            let help = ["?        HINTS", "CTRL-N   NEW GAME", "CTRL-S   SHARE", "ENTER    SUBMIT", "PGUP/DN  SCROLL", "ESC      QUIT"];
+            ("KEYS".to_string(), help.iter().map(|line| Spans::from(*line)).collect())
+        }
+        else
+        {
+            (self.side_title.clone(), self.side.clone())
+        };
+        let side = Paragraph::new(text)
+            .wrap(Wrap{ trim: false })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(side, area);
+    }
+
+    //键盘：每个字母按目前得到的最好结果着色
+    fn draw_keyboard<B: Backend>(&self, f: &mut Frame<B>, area: Rect)
+    {
+        let block = Block::default().borders(Borders::ALL).title("KEYBOARD");
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        for (code, key) in self.keys(inner)
+        {
+            let color = match code
+            {
+                KeyCode::Char(letter) => self.keyboard.get(&letter),
+                _ => None,
+            };
+            let style = match color.and_then(|color| self.theme.color(*color))
+            {
+                Some(bg) => Style::default().fg(Color::Black).bg(tui_color(bg)),
+                None if color.is_some() => Style::default(),
+                None => Style::default().fg(Color::White).add_modifier(Modifier::REVERSED),
+            };
+            let label = match code
+            {
+                KeyCode::Char(letter) => self.theme.key_mark(letter, color.copied()),
+                _ => key_label(code),
+            };
+            f.render_widget(Paragraph::new(label).alignment(Alignment::Center).style(style), key);
+        }
+    }
+
+    //状态栏：上一条消息，以及提示与当前输入
+    fn draw_status<B: Backend>(&self, f: &mut Frame<B>, area: Rect, input: &str)
+    {
+        let mut prompt = vec![Span::raw(self.prompt.clone())];
+        if !self.typing()
+        {
+            prompt.push(Span::raw(" "));
+            prompt.push(Span::styled(input.to_string(), Style::default().fg(Color::Blue)));
+        }
+        let text = vec![
+            Spans::from(Span::styled(self.message.clone(), Style::default().add_modifier(Modifier::BOLD))),
+            Spans::from(prompt),
+        ];
+        let status = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+        f.render_widget(status, area);
+    }
+}
+
+//界面各部分所在的区域
+struct Areas
+{
+    boards: Rect,
+    side: Rect,
+    keyboard: Rect,
+    status: Rect,
+}
+
+//棋盘的排列方式
+struct Grid
+{
+    columns: usize,
+    visible: usize,
+    first: usize,
+}
+
+//QWERTY 键盘上每个按键所在的位置
+pub fn keyboard_keys(area: Rect) -> Vec<(KeyCode, Rect)>
+{
+    layout_keys(area, &QWERTY)
+}
+
+//键盘布局 layout 中每个按键所在的位置，每行依次向右错开，最后一行两端为 ENTER 与退格
+pub fn layout_keys<S: AsRef<str>>(area: Rect, layout: &[S]) -> Vec<(KeyCode, Rect)>
+{
+    let mut keys = Vec::new();
+    for (row, line) in layout.iter().enumerate()
+    {
+        let mut cells: Vec<Option<KeyCode>> = line
+            .as_ref()
+            .chars()
+            .map(|letter| if letter == ' ' { None } else { Some(KeyCode::Char(letter)) })
+            .collect();
+        if row + 1 == layout.len()
+        {
+            let indent = cells.iter().take_while(|cell| cell.is_none()).count();
+            cells.splice(..indent, [Some(KeyCode::Enter), None]);
+            cells.extend([None, Some(KeyCode::Backspace)]);
+        }
+        let y = area.y + row as u16;
+        let mut x = area.x;
+        for cell in cells
+        {
+            let code = match cell
+            {
+                Some(code) => code,
+                None =>
+                {
+                    x += 1;
+                    continue;
+                }
+            };
+            let key = Rect::new(x, y, key_label(code).chars().count() as u16 + KEY_WIDTH - 1, 1);
+            if fits(key, area)
+            {
+                keys.push((code, key));
+            }
+            x += key.width;
+        }
+    }
+    keys
+}
+
+fn key_label(code: KeyCode) -> String
+{
+    match code
+    {
+        KeyCode::Char(letter) => letter.to_string(),
+        KeyCode::Enter => "ENTER".to_string(),
+        _ => "BKSP".to_string(),
+    }
+}
+
+//格子完全位于区域内时才绘制，避免越界
+fn fits(rect: Rect, area: Rect) -> bool
+{
+    rect.x >= area.x && rect.y >= area.y && rect.right() <= area.right() && rect.bottom() <= area.bottom()
+}
+
+//格子的边框样式与内容样式
+fn tile_styles(theme: Theme, letter: Option<char>, color: Option<LetterFeedback>) -> (Style, Style)
+{
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    match color.map(|color| theme.color(color))
+    {
+        Some(Some(bg)) =>
+        {
+            let bg = tui_color(bg);
+            let fg = if theme.bold() { Color::White } else { Color::Black };
+            (Style::default().fg(bg), bold.fg(fg).bg(bg))
+        }
+        Some(None) => (Style::default().fg(Color::White), bold),
+        None if letter.is_some() => (Style::default().fg(Color::White), bold),
+        None => (Style::default().fg(Color::DarkGray), Style::default()),
+    }
+}
+
+fn tile_style(theme: Theme, letter: Option<char>, color: Option<LetterFeedback>) -> Style
+{
+    tile_styles(theme, letter, color).1
+}
+
+//格子中的文字，单色主题下用符号表示结果
+fn tile_text(theme: Theme, letter: Option<char>, color: Option<LetterFeedback>) -> String
+{
+    letter.map_or(String::new(), |letter| theme.mark(dictionary::upper(letter), color))
+}
+
+fn tile_widget(theme: Theme, letter: Option<char>, color: Option<LetterFeedback>) -> Paragraph<'static>
+{
+    let (border, style) = tile_styles(theme, letter, color);
+    Paragraph::new(tile_text(theme, letter, color))
+        .alignment(Alignment::Center)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).border_style(border))
+}
+
+//主题中的颜色转换为TUI使用的颜色
+pub fn tui_color(color: crossterm::style::Color) -> Color
+{
+    use crossterm::style::Color as Term;
+    match color
+    {
+        Term::Green => Color::Green,
+        Term::Yellow => Color::Yellow,
+        Term::Red => Color::Red,
+        Term::DarkGrey => Color::DarkGray,
+        Term::White => Color::White,
+        Term::Black => Color::Black,
+        Term::Rgb{ r, g, b } => Color::Rgb(r, g, b),
+        _ => Color::Reset,
+    }
+}
+
+//绘制界面并读取一行输入，最多 limit 个字符，遇到按键操作时返回该操作
+//播放动画时每隔 TICK 推进动画并重新绘制，按下任意键则跳过动画
+pub fn read_input<B: Backend>(terminal: &mut Terminal<B>, screen: &mut Screen, limit: usize) -> io::Result<Result<String, Action>>
+{
+    let mut input = String::new();
+    loop
+    {
+        terminal.draw(|f| screen.draw(f, &input))?;
+        if screen.animating() && !event::poll(TICK)?
+        {
+            screen.advance(TICK);
+            continue;
+        }
+
+        //处理输入，鼠标点击键盘上的按键与按下该键相同
+        let (code, modifiers) = match event::read()?
+        {
+            Event::Key(event) => (event.code, event.modifiers),
+            Event::Mouse(event) => match event.kind
+            {
+                MouseEventKind::Down(MouseButton::Left) => match screen.key_at(terminal.size()?, event.column, event.row)
+                {
+                    Some(code) => (code, KeyModifiers::NONE),
+                    None => continue,
+                },
+                MouseEventKind::ScrollUp => (KeyCode::PageUp, KeyModifiers::NONE),
+                MouseEventKind::ScrollDown => (KeyCode::PageDown, KeyModifiers::NONE),
+                _ => continue,
+            },
+            //终端大小改变后重新绘制
+            Event::Resize(_, _) => continue,
+            _ => continue,
+        };
+        screen.animation = None;
+        match code
+        {
+            KeyCode::Char('n') if modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Ok(Err(Action::NewGame));
+            }
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                return Ok(Err(Action::Share));
+            }
+            KeyCode::Char('?') =>
+            {
+                return Ok(Err(Action::Hint));
+            }
+            KeyCode::Char(c) if input.chars().count() < limit && !modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                input.push(c);
+            }
+            KeyCode::Backspace =>
+            {
+                input.pop();
+            }
+            KeyCode::PageUp =>
+            {
+                screen.scroll = (screen.scroll + 1).min(screen.rows);
+            }
+            KeyCode::PageDown =>
+            {
+                screen.scroll = screen.scroll.saturating_sub(1);
+            }
+            KeyCode::Enter =>
+            {
+                return Ok(Ok(input));
+            }
+            KeyCode::Esc =>
+            {
+                return Ok(Err(Action::Quit));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+use crate::dictionary;
+use crate::error::WordleError;
+use crate::feedback::{LetterFeedback, Pattern};
+use crate::file::Round;
+use crate::game::{self, Word};
+use crate::hard_mode::{Difficulty, HardModeRules};
+use crate::solver;
+
+//每局默认允许的最大猜测次数
+pub const DEFAULT_MAX_GUESSES: usize = 6;
+
+//一局游戏所处的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status
+{
+    Playing,
+    Won,
+    Lost,
+}
+
+//一次合法猜测的结果，附带本次比较得到的颜色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome
+{
+    Continue(Pattern),
+    Won(Pattern),
+    Lost(Pattern),
+}
+
+//答案：固定的单词，或对抗模式中仍然可能的全部单词
+#[derive(Debug, Clone)]
+enum Answer
+{
+    Fixed(Word),
+    Adversarial(Vec<Word>),
+}
+
+//不依赖任何输入输出的单局游戏状态机
+#[derive(Debug, Clone)]
+pub struct GameSession
+{
+    answer: Answer,
+    guess_file: Vec<String>,
+    rules: Option<HardModeRules>,
+    max_guesses: usize,
+    guesses: Vec<String>,
+    results: Vec<Pattern>,
+    keyboard: HashMap<char, LetterFeedback>,
+    status: Status,
+}
+
+impl GameSession
+{
+    //guess_file 为空时使用内置词库
+    pub fn new(answer: Word, guess_file: &[String], difficulty: Difficulty, max_guesses: usize) -> GameSession
+    {
+        GameSession::with_answer(Answer::Fixed(answer), guess_file, difficulty, max_guesses)
+    }
+
+    //对抗模式：不预先确定答案，每次猜测后保留候选最多的一组
+    pub fn adversarial(candidates: Vec<Word>, guess_file: &[String], difficulty: Difficulty, max_guesses: usize) -> GameSession
+    {
+        assert!(!candidates.is_empty(), "adversarial session needs candidates");
+        GameSession::with_answer(Answer::Adversarial(candidates), guess_file, difficulty, max_guesses)
+    }
+
+    fn with_answer(answer: Answer, guess_file: &[String], difficulty: Difficulty, max_guesses: usize) -> GameSession
+    {
+        GameSession
+        {
+            answer,
+            guess_file: guess_file.to_vec(),
+            rules: HardModeRules::for_difficulty(difficulty),
+            max_guesses,
+            guesses: Vec::new(),
+            results: Vec::new(),
+            keyboard: HashMap::new(),
+            status: Status::Playing,
+        }
+    }
+
+    //检查猜测是否合法，合法时返回大写形式
+    pub fn check_guess(&self, word: &str) -> Result<String, WordleError>
+    {
+        if self.status != Status::Playing
+        {
+            return Err(WordleError::invalid_guess(word, "GAME OVER"));
+        }
+        let word = dictionary::uppercase(word.trim());
+        if let Some(rules) = &self.rules
+        {
+            rules.check(&word).map_err(|reason| WordleError::invalid_guess(&word, &reason))?;
+        }
+        game::guess_is_valid(&word, &self.guess_file)
+    }
+
+    //提交一次猜测并更新全部记录
+    pub fn submit_guess(&mut self, word: &str) -> Result<Outcome, WordleError>
+    {
+        let guess = self.check_guess(word)?;
+        let result = match &mut self.answer
+        {
+            Answer::Fixed(answer) => answer.compare(&guess),
+            Answer::Adversarial(candidates) =>
+            {
+                let (result, bucket) = solver::adversarial(&guess, candidates);
+                *candidates = bucket;
+                result
+            }
+        };
+        for (letter, color) in guess.chars().zip(result.letters())
+        {
+            let best = self.keyboard.entry(letter).or_insert(color);
+            if color > *best
+            {
+                *best = color;
+            }
+        }
+        if let Some(rules) = &mut self.rules
+        {
+            rules.record(&guess, result);
+        }
+        self.guesses.push(guess);
+        self.results.push(result);
+
+        if result.is_correct()
+        {
+            self.status = Status::Won;
+            Ok(Outcome::Won(result))
+        }
+        else if self.guesses.len() >= self.max_guesses
+        {
+            self.status = Status::Lost;
+            Ok(Outcome::Lost(result))
+        }
+        else
+        {
+            Ok(Outcome::Continue(result))
+        }
+    }
+
+    //对抗模式中答案在结束前尚未确定，此时为空字符串
+    pub fn answer(&self) -> &str
+    {
+        match &self.answer
+        {
+            Answer::Fixed(answer) => &answer.origin,
+            Answer::Adversarial(candidates) if self.is_over() => &candidates[0].origin,
+            Answer::Adversarial(_) => "",
+        }
+    }
+
+    //单词长度
+    pub fn length(&self) -> usize
+    {
+        match &self.answer
+        {
+            Answer::Fixed(answer) => answer.length(),
+            Answer::Adversarial(candidates) => candidates[0].length(),
+        }
+    }
+
+    pub fn guesses(&self) -> &[String]
+    {
+        &self.guesses
+    }
+
+    pub fn results(&self) -> &[Pattern]
+    {
+        &self.results
+    }
+
+    //每个字母目前得到的最好颜色，用于显示键盘
+    pub fn keyboard(&self) -> &HashMap<char, LetterFeedback>
+    {
+        &self.keyboard
+    }
+
+    pub fn status(&self) -> Status
+    {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool
+    {
+        self.status != Status::Playing
+    }
+
+    pub fn max_guesses(&self) -> usize
+    {
+        self.max_guesses
+    }
+
+    //剩余的猜测次数
+    pub fn remaining(&self) -> usize
+    {
+        self.max_guesses - self.guesses.len()
+    }
+
+    //转换为存档中的一局记录
+    pub fn round(&self) -> Round
+    {
+        Round
+        {
+            answer: self.answer().to_string(),
+            guesses: self.guesses.clone(),
+            answers: Vec::new(),
+        }
+    }
+}
+
+//多个棋盘同时进行的一局游戏，每次猜测作用于所有未完成的棋盘
+//每个棋盘独立结束，全部猜中为胜利，次数用尽时仍有未猜中的棋盘为失败
+#[derive(Debug, Clone)]
+pub struct MultiSession
+{
+    boards: Vec<GameSession>,
+    guesses: Vec<String>,
+    max_guesses: usize,
+    status: Status,
+}
+
+impl MultiSession
+{
+    //max_guesses 为单个棋盘的次数，每多一个棋盘多一次机会
+    pub fn new(answers: Vec<Word>, guess_file: &[String], difficulty: Difficulty, max_guesses: usize) -> MultiSession
+    {
+        let max_guesses = total_guesses(answers.len(), max_guesses);
+        MultiSession
+        {
+            boards: answers
+                .into_iter()
+                .map(|answer| GameSession::new(answer, guess_file, difficulty, max_guesses))
+                .collect(),
+            guesses: Vec::new(),
+            max_guesses,
+            status: Status::Playing,
+        }
+    }
+
+    //单个棋盘的对抗模式
+    pub fn adversarial(candidates: Vec<Word>, guess_file: &[String], difficulty: Difficulty, max_guesses: usize) -> MultiSession
+    {
+        MultiSession
+        {
+            boards: vec![GameSession::adversarial(candidates, guess_file, difficulty, max_guesses)],
+            guesses: Vec::new(),
+            max_guesses,
+            status: Status::Playing,
+        }
+    }
+
+    //检查猜测对所有未完成的棋盘是否都合法
+    pub fn check_guess(&self, word: &str) -> Result<String, WordleError>
+    {
+        if self.status != Status::Playing
+        {
+            return Err(WordleError::invalid_guess(word, "GAME OVER"));
+        }
+        let mut guess = Err(WordleError::invalid_guess(word, "GAME OVER"));
+        for board in self.boards.iter().filter(|board| !board.is_over())
+        {
+            guess = Ok(board.check_guess(word)?);
+        }
+        guess
+    }
+
+    //提交一次猜测，返回提交后的状态
+    pub fn submit_guess(&mut self, word: &str) -> Result<Status, WordleError>
+    {
+        let guess = self.check_guess(word)?;
+        for board in self.boards.iter_mut().filter(|board| !board.is_over())
+        {
+            board.submit_guess(&guess)?;
+        }
+        self.guesses.push(guess);
+
+        self.status = if self.boards.iter().all(|board| board.status() == Status::Won)
+        {
+            Status::Won
+        }
+        else if self.guesses.len() >= self.max_guesses
+        {
+            Status::Lost
+        }
+        else
+        {
+            Status::Playing
+        };
+        Ok(self.status)
+    }
+
+    pub fn boards(&self) -> &[GameSession]
+    {
+        &self.boards
+    }
+
+    //第一个尚未完成的棋盘，用于提示
+    pub fn current_board(&self) -> Option<&GameSession>
+    {
+        self.boards.iter().find(|board| !board.is_over())
+    }
+
+    pub fn guesses(&self) -> &[String]
+    {
+        &self.guesses
+    }
+
+    pub fn answers(&self) -> Vec<&str>
+    {
+        self.boards.iter().map(|board| board.answer()).collect()
+    }
+
+    pub fn length(&self) -> usize
+    {
+        self.boards[0].length()
+    }
+
+    pub fn status(&self) -> Status
+    {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool
+    {
+        self.status != Status::Playing
+    }
+
+    pub fn remaining(&self) -> usize
+    {
+        self.max_guesses - self.guesses.len()
+    }
+
+    //转换为存档中的一局记录，单个棋盘时与普通的一局相同
+    pub fn round(&self) -> Round
+    {
+        let answers = self.answers();
+        Round
+        {
+            answer: answers[0].to_string(),
+            guesses: self.guesses.clone(),
+            answers: if answers.len() > 1 { answers.iter().map(|answer| answer.to_string()).collect() } else { Vec::new() },
+        }
+    }
+}
+
+//boards 个棋盘时每局允许的猜测次数
+pub fn total_guesses(boards: usize, max_guesses: usize) -> usize
+{
+    max_guesses + boards.max(1) - 1
+}
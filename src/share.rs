@@ -0,0 +1,125 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::feedback::LetterFeedback;
+use crate::session::{MultiSession, Status};
+use crate::theme::Theme;
+
+//分享结果的格式：彩色方块，或不支持 emoji 时使用的 ASCII 字符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShareFormat
+{
+    #[default]
+    Emoji,
+    Ascii,
+}
+
+impl ShareFormat
+{
+    //一个字母的结果，高对比度与色盲主题使用橙色 / 蓝色方块
+    fn square(&self, theme: Theme, feedback: LetterFeedback) -> &'static str
+    {
+        let orange = matches!(theme, Theme::HighContrast | Theme::ColourBlind);
+        match (self, feedback)
+        {
+            (ShareFormat::Ascii, LetterFeedback::Correct) => "G",
+            (ShareFormat::Ascii, LetterFeedback::Present) => "Y",
+            (ShareFormat::Ascii, LetterFeedback::Absent) => "-",
+            (ShareFormat::Emoji, LetterFeedback::Correct) if orange => "\u{1F7E7}",
+            (ShareFormat::Emoji, LetterFeedback::Present) if orange => "\u{1F7E6}",
+            (ShareFormat::Emoji, LetterFeedback::Correct) => "\u{1F7E9}",
+            (ShareFormat::Emoji, LetterFeedback::Present) => "\u{1F7E8}",
+            (ShareFormat::Emoji, LetterFeedback::Absent) => "\u{2B1B}",
+        }
+    }
+}
+
+impl fmt::Display for ShareFormat
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            ShareFormat::Emoji => write!(f, "emoji"),
+            ShareFormat::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+impl FromStr for ShareFormat
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ShareFormat, String>
+    {
+        match s.to_lowercase().as_str()
+        {
+            "emoji" => Ok(ShareFormat::Emoji),
+            "ascii" => Ok(ShareFormat::Ascii),
+            _ => Err("INVALID SHARE FORMAT".to_string()),
+        }
+    }
+}
+
+//可以分享的结果：标题为 "Wordle 817 4/6*"，失败时次数为 X，困难模式加 *，没有日期时省略
+//之后每次猜测一行方块，不包含字母，多棋盘时各棋盘之间空一行
+pub fn share_text(session: &MultiSession, day: Option<u64>, hard: bool, format: ShareFormat, theme: Theme) -> String
+{
+    let count = match session.status()
+    {
+        Status::Won => session.guesses().len().to_string(),
+        _ => "X".to_string(),
+    };
+    let total = session.guesses().len() + session.remaining();
+    let mut text = match day
+    {
+        Some(day) => format!("Wordle {} {}/{}", day, count, total),
+        None => format!("Wordle {}/{}", count, total),
+    };
+    if hard
+    {
+        text.push('*');
+    }
+    text.push('\n');
+    for board in session.boards()
+    {
+        text.push('\n');
+        for result in board.results()
+        {
+            for feedback in result.letters()
+            {
+                text.push_str(format.square(theme, feedback));
+            }
+            text.push('\n');
+        }
+    }
+    text
+}
+
+//OSC 52 控制序列，支持的终端收到后把 text 复制到剪贴板
+pub fn clipboard_sequence(text: &str) -> String
+{
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+//标准 base64 编码，末尾用 = 补齐
+fn base64(bytes: &[u8]) -> String
+{
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3)
+    {
+        let value = chunk.iter().enumerate().fold(0u32, |value, (i, &byte)| value | (byte as u32) << (16 - 8 * i));
+        for i in 0..4
+        {
+            if i <= chunk.len()
+            {
+                encoded.push(TABLE[(value >> (18 - 6 * i) & 0x3f) as usize] as char);
+            }
+            else
+            {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
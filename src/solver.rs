@@ -1,137 +1,155 @@
-use crate::{builtin_words::{ACCEPTABLE, FINAL}, game::{self, cmp_val, Word}};
-use std::{collections::{BinaryHeap, HashMap}, vec};
+use crate::{builtin_words::FINAL, game::{self, cmp_val, Word}};
+use crate::dictionary::Dictionary;
+use crate::feedback::Pattern;
+use crate::hard_mode::Difficulty;
+use crate::matrix::FeedbackMatrix;
+use crate::session::{GameSession, Status};
+use crate::theme::Theme;
+use std::collections::{BTreeMap, BinaryHeap};
 use std::cmp::{Ordering, min};
 use rayon::prelude::*;
-use std::sync::Arc;
 
-//单步最优
-pub fn help(record: &Vec<String>, full_result: &Vec<[u8; 5]>) -> (Vec<(String, f64)>, Vec<(String, f64)>) {
+//单词及其信息熵
+pub type Scores = Vec<(String, f64)>;
 
-    //寻找可能答案
-    let possible_answers: Vec<String> = ACCEPTABLE
-        .par_iter()
-        .filter_map(|&possible_answer| {
-            let uppercased = possible_answer.to_uppercase();
-            let mut count: usize = 0;
-            let mut possible = true;
-            for guess in record.iter() {
-                if Word::new(&uppercased).compare(&guess) != full_result[count] {
-                    possible = false;
-                    break;
-                }
-                count += 1;
-            }
-            if possible {
-                Some(uppercased)
-            } else {
-                None
-            }
+//由各类别的大小计算信息熵
+//只有一类时结果为 -0.0，加上 0.0 使其变为 0.0，避免输出 -0.00
+fn entropy<I: Iterator<Item = usize>>(counts: I, total: f64) -> f64 {
+    counts
+        .filter(|&count| count > 0)
+        .map(|count| {
+            let p = count as f64 / total;
+            p * (-p.log2())
         })
-        .collect();
+        .sum::<f64>()
+        + 0.0
+}
 
-    let possible_answers_arc = Arc::new(possible_answers);
-    let possible_answers_len = possible_answers_arc.len() as f64;
+//单步最优
+pub fn help(matrix: &FeedbackMatrix, record: &[String], full_result: &[Pattern]) -> (Scores, Scores) {
+    //寻找可能答案
+    let possible_answers = matrix.candidates(record, full_result);
+    let mut is_possible = vec![false; matrix.answers().len()];
+    for &answer in possible_answers.iter() {
+        is_possible[answer] = true;
+    }
+    let total = possible_answers.len() as f64;
 
     //对所有候选词进行遍历计算信息熵
-    let mut info_sorted: Vec<(String, f64)> = ACCEPTABLE
-        .par_iter()
-        .filter_map(|&possible_guess| {
-            let uppercased_guess = possible_guess.to_uppercase();
-            if record.contains(&uppercased_guess) {
-                return None;
+    let mut info_sorted: Scores = (0..matrix.guesses().len())
+        .into_par_iter()
+        .filter(|&guess| !record.contains(&matrix.guesses()[guess]))
+        .map(|guess| {
+            let row = matrix.row(guess);
+            let mut counts = vec![0usize; matrix.pattern_count()];
+            for &answer in possible_answers.iter() {
+                counts[row[answer] as usize] += 1;
             }
-            let mut possibilities: HashMap<[u8; 5], usize> = HashMap::new();
-            for possible_answer in possible_answers_arc.iter() {
-                let tmp = possibilities.entry(Word::new(&possible_answer).compare(&uppercased_guess)).or_insert(0);
-                *tmp += 1;
-            }
-
-            let entropy: f64 = possibilities
-                .values()
-                .map(|&count| {
-                    let tmp = count as f64 / possible_answers_len;
-                    tmp * (-tmp.log2())
-                })
-                .sum();
-
-            Some((uppercased_guess, entropy))
+            (matrix.guesses()[guess].clone(), entropy(counts.into_iter(), total))
         })
         .collect();
 
     //排序
-    info_sorted.par_sort_by(|a, b| cmp_val(a, b));
+    info_sorted.par_sort_by(cmp_val);
 
     //筛选出可能是答案的
-    let help: Vec<(String, f64)> = info_sorted
-        .par_iter()
-        .filter(|x| possible_answers_arc.contains(&x.0))
+    let help: Scores = info_sorted
+        .iter()
+        .filter(|x| matrix.answer_index(&x.0).is_some_and(|answer| is_possible[answer]))
         .cloned()
         .collect();
 
     (info_sorted, help)
 }
 
-//计算复杂熵的两个函数
-
-//优化后
-fn compute_entropy(words: &Vec<String>, possible_answers: &Vec<String>) -> f64 {
-    let possibilities: HashMap<Vec<[u8; 5]>, usize> = possible_answers
-        .par_iter()
-        .map(|possible_answer| {
-            let results: Vec<[u8; 5]> = words
-                .iter()
-                .map(|word| Word::new(&possible_answer).compare(word))
-                .collect();
-            results
-        })
-        .fold(HashMap::new, |mut acc, results| {
-            *acc.entry(results).or_insert(0) += 1;
-            acc
-        })
-        .reduce(HashMap::new, |mut acc, map| {
-            for (key, value) in map {
-                *acc.entry(key).or_insert(0) += value;
-            }
-            acc
-        });
+//某个猜测对一组可能答案（matrix 中的下标）的信息熵，猜测可以不在矩阵中
+pub fn guess_entropy(matrix: &FeedbackMatrix, guess: &str, possible_answers: &[usize]) -> f64 {
+    let mut counts = vec![0usize; matrix.pattern_count()];
+    for &answer in possible_answers.iter() {
+        counts[matrix.pattern(guess, answer).code() as usize] += 1;
+    }
+    entropy(counts.into_iter(), possible_answers.len() as f64)
+}
 
-    let entropy: f64 = possibilities
-        .iter()
-        .map(|(_, count)| {
-            let p = *count as f64 / possible_answers.len() as f64;
-            p * (-p.log2())
-        })
-        .sum();
+//按某个猜测的结果将候选答案分组，键为比较结果
+pub fn partition(guess: &str, candidates: &[Word]) -> BTreeMap<Pattern, Vec<Word>> {
+    let mut buckets: BTreeMap<Pattern, Vec<Word>> = BTreeMap::new();
+    for candidate in candidates.iter() {
+        buckets.entry(candidate.compare(guess)).or_default().push(candidate.clone());
+    }
+    buckets
+}
 
-    entropy
-} 
+//对抗模式：保留候选最多的一组，数量相同时取结果编码最小（提示最少）的一组
+pub fn adversarial(guess: &str, candidates: &[Word]) -> (Pattern, Vec<Word>) {
+    let mut best: Option<(Pattern, Vec<Word>)> = None;
+    for (pattern, bucket) in partition(guess, candidates) {
+        let larger = match &best {
+            Some((_, words)) => bucket.len() > words.len(),
+            None => true,
+        };
+        if larger {
+            best = Some((pattern, bucket));
+        }
+    }
+    best.expect("no candidates left")
+}
 
-//优化前
-/*fn compute_entropy(words: &Vec<String>, possible_answers: &Vec<String>) -> f64
-{
-    let mut possibilities: HashMap<Vec<[u8; 5]>, usize> = HashMap::new();
-    for possible_answer in possible_answers.iter()
-    {
-        let mut results: Vec<[u8; 5]> = Vec::new();
-        for word in words.iter()
-        {
-            results.push(Word::new(&possible_answer).compare(word));
+//按一组猜测的结果将可能答案分类，返回每个答案的类别编号与类别数
+fn classify(words: &[usize], possible_answers: &[usize], matrix: &FeedbackMatrix) -> (Vec<usize>, usize) {
+    let mut classes = vec![0; possible_answers.len()];
+    let mut class_count = 1;
+    let pattern_count = matrix.pattern_count();
+    for &word in words.iter() {
+        let row = matrix.row(word);
+        let mut index = vec![usize::MAX; class_count * pattern_count];
+        let mut next = 0;
+        for (class, &answer) in classes.iter_mut().zip(possible_answers.iter()) {
+            let key = *class * pattern_count + row[answer] as usize;
+            if index[key] == usize::MAX {
+                index[key] = next;
+                next += 1;
+            }
+            *class = index[key];
         }
-        let tmp = possibilities.entry(results).or_insert(0);
-        *tmp += 1;
+        class_count = next;
     }
-    let mut entropy: f64 = 0.0;
-    for p in possibilities.iter()
-    {
-        let tmp: f64 = *p.1 as f64 / possible_answers.len() as f64;
-        entropy += tmp * (-tmp.log2());
+    (classes, class_count)
+}
+
+//预先计算 c * log2(c)，避免在内层循环中反复求对数
+fn clog_table(total: usize) -> Vec<f64> {
+    (0..=total).map(|count| if count == 0 { 0.0 } else { count as f64 * (count as f64).log2() }).collect()
+}
+
+//在已有分类的基础上再猜一个词后的信息熵，counts 与 keys 为可复用的空间
+//H = log2(n) - sum(c * log2(c)) / n，第二遍遍历时每个类别只在第一次遇到时计入，随后清零
+fn refine_entropy(classes: &[usize], guess: usize, possible_answers: &[usize], matrix: &FeedbackMatrix, table: &[f64], counts: &mut [u16], keys: &mut Vec<u32>) -> f64 {
+    let row = matrix.row(guess);
+    let pattern_count = matrix.pattern_count();
+    keys.clear();
+    keys.extend(
+        classes
+            .iter()
+            .zip(possible_answers.iter())
+            .map(|(&class, &answer)| (class * pattern_count + row[answer] as usize) as u32),
+    );
+    for &key in keys.iter() {
+        counts[key as usize] += 1;
+    }
+    let mut sum = 0.0;
+    for &key in keys.iter() {
+        sum += table[counts[key as usize] as usize];
+        counts[key as usize] = 0;
     }
-    entropy
-}*/
+    let total = possible_answers.len() as f64;
+    //只有一类时两项相等，舍入误差可能使结果略小于 0
+    (total.log2() - sum / total).max(0.0)
+}
 
 #[derive(Debug, Clone)]
 struct Path {
-    words: Vec<String>,
+    words: Vec<usize>,
     entropy: f64,
 }
 
@@ -145,182 +163,103 @@ impl Eq for Path {}
 
 impl PartialOrd for Path {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        other.entropy.partial_cmp(&self.entropy)
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Path {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+        other.entropy.partial_cmp(&self.entropy).unwrap_or(Ordering::Equal)
     }
 }
 
-//全局最优
-pub fn solve(record: &Vec<String>, full_result: &Vec<[u8; 5]>, time: i32) -> Vec<(String, f64)> {
-    let mut possible_answers: Vec<String> = Vec::new();
-
-    //使用并行计算筛选可能的答案
-    possible_answers = ACCEPTABLE.par_iter()
-        .filter_map(|possible_answer| {
-            let mut count: usize = 0;
-            let mut possible: bool = true;
-            for guess in record.iter() {
-                if Word::new(&possible_answer.to_uppercase()).compare(&guess) != full_result[count] {
-                    possible = false;
-                    break;
-                }
-                count += 1;
-            }
-            if possible {
-                Some(possible_answer.to_uppercase())
-            } else {
-                None
+//在 prefix 之后尝试候选词，结果放入只保留前10个的优先队列
+//singles 为按单步信息熵从大到小排序的候选词，由于 H(A, B) <= H(A) + H(B)，
+//当上界已经不可能进入前10时即可停止
+fn extend(prefix: &Path, singles: &[(usize, f64)], possible_answers: &[usize], matrix: &FeedbackMatrix, heap: &mut BinaryHeap<Path>) {
+    let (classes, class_count) = classify(&prefix.words, possible_answers, matrix);
+    let table = clog_table(possible_answers.len());
+    for chunk in singles.chunks(256) {
+        if let Some(worst) = heap.peek() {
+            if heap.len() >= 10 && prefix.entropy + chunk[0].1 <= worst.entropy {
+                break;
             }
-        })
-        .collect();
+        }
+        let paths: Vec<Path> = chunk
+            .par_iter()
+            .filter(|(guess, _)| !prefix.words.contains(guess))
+            .map_init(
+                || (vec![0u16; class_count * matrix.pattern_count()], Vec::new()),
+                |(counts, keys), &(guess, _)| {
+                    let mut words = prefix.words.clone();
+                    words.push(guess);
+                    Path {
+                        words,
+                        entropy: refine_entropy(&classes, guess, possible_answers, matrix, &table, counts, keys),
+                    }
+                },
+            )
+            .collect();
+        heap.extend(paths);
+
+        //只保留前10个最优路径
+        while heap.len() > 10 {
+            heap.pop();
+        }
+    }
+}
 
-    //最后一步用单步最优即可    
+//全局最优
+pub fn solve(matrix: &FeedbackMatrix, record: &[String], full_result: &[Pattern], time: i32) -> Scores {
+    //筛选可能的答案
+    let possible_answers = matrix.candidates(record, full_result);
+
+    //最后一步用单步最优即可
     if possible_answers.len() <= 5 || time <= 1 {
-        let (_, help) = help(record, full_result);
+        let (_, help) = help(matrix, record, full_result);
         return help;
     }
 
-    //使用并行计算来初始化优先队列
-    let mut solve: BinaryHeap<Path> = ACCEPTABLE.par_iter()
-        .filter(|&&possible_guess| !record.contains(&possible_guess.to_uppercase()))
-        .map(|&possible_guess| {
-            let new_words = vec![possible_guess.to_uppercase()];
-            Path {
-                words: new_words.clone(),
-                entropy: compute_entropy(&new_words, &possible_answers),
-            }
-        })
+    //所有候选词的单步信息熵，从大到小排序
+    let (classes, _) = classify(&[], &possible_answers, matrix);
+    let table = clog_table(possible_answers.len());
+    let mut singles: Vec<(usize, f64)> = (0..matrix.guesses().len())
+        .into_par_iter()
+        .filter(|&guess| !record.contains(&matrix.guesses()[guess]))
+        .map_init(
+            || (vec![0u16; matrix.pattern_count()], Vec::new()),
+            |(counts, keys), guess| (guess, refine_entropy(&classes, guess, &possible_answers, matrix, &table, counts, keys)),
+        )
         .collect();
+    singles.par_sort_by(cmp_val);
 
-    //只保留前10个最优路径
-    while solve.len() > 10 {
-        solve.pop();
-    }
+    //初始化优先队列，只保留前10个最优路径
+    let mut solve: BinaryHeap<Path> = singles
+        .iter()
+        .take(10)
+        .map(|&(guess, entropy)| Path { words: vec![guess], entropy })
+        .collect();
 
     //迭代后续情况并进行减枝
     let t = min(time, 3);
     for _i in 1..t {
-        let mut new_heap: BinaryHeap<Path> = solve.par_iter()
-            .flat_map(|tmp_path| {
-                ACCEPTABLE.par_iter()
-                    .filter(|&&possible_guess| {
-                        !record.contains(&possible_guess.to_uppercase()) &&
-                        !tmp_path.words.contains(&possible_guess.to_uppercase())
-                    })
-                    .map(|&possible_guess| {
-                        let mut new_words = tmp_path.words.clone();
-                        new_words.push(possible_guess.to_uppercase());
-                        Path {
-                            words: new_words.clone(),
-                            entropy: compute_entropy(&new_words, &possible_answers),
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .collect();
-
-        // 只保留前10个最优路径
-        while new_heap.len() > 10 {
-            new_heap.pop();
+        let mut new_heap: BinaryHeap<Path> = BinaryHeap::new();
+        for tmp_path in solve.into_sorted_vec() {
+            extend(&tmp_path, &singles, &possible_answers, matrix, &mut new_heap);
         }
         solve = new_heap;
     }
 
-    let mut info: Vec<(String, f64)> = Vec::new();
+    let mut info: Scores = Vec::new();
     while let Some(path) = solve.pop() {
-        info.push((path.words.first().unwrap().clone(), path.entropy));
+        info.push((matrix.guesses()[path.words[0]].clone(), path.entropy));
     }
     info.reverse();
     info
 }
 
-/*pub fn solve(record: &Vec<String>, full_result: &Vec<[u8; 5]>, time: i32) -> Vec<(String, f64)>
-{
-    let mut possible_answers: Vec<String> = Vec::new();
-
-    //实现优先队列
-    let mut solve: BinaryHeap<Path> = BinaryHeap::new();
-
-    for possible_answer in ACCEPTABLE
-    {
-        let mut count: usize = 0;
-        let mut possible: bool = true;
-        for guess in record.iter()
-        {
-            if Word::new(&possible_answer.to_uppercase()).compare(&guess) != full_result[count]
-            {
-                possible = false;
-                break;
-            }
-            count += 1;
-        }
-        if possible
-        {
-            possible_answers.push(possible_answer.to_uppercase());
-        }
-    }
-
-    //最后几步，采用单步最优即可
-    if possible_answers.len() <= 5 || time <= 1
-    {
-        let (_, help) = help(record, full_result);
-        return help;
-    }
-
-    for possible_guess in ACCEPTABLE
-    {
-        if record.contains(&possible_guess.to_uppercase())
-        {
-            continue;
-        }
-        let new_words = vec![possible_guess.to_uppercase()];
-        solve.push(Path{ words: new_words.clone(), entropy: compute_entropy(&new_words, &possible_answers) });
-        if solve.len() > 10
-        {
-            solve.pop();
-        }
-    }
-    let t = min(time, 3);
-    for _i in 1..t
-    {
-        let mut new: BinaryHeap<Path> = BinaryHeap::new();
-        for tmp_path in solve.iter()
-        {
-            println!("HERE");
-            for possible_guess in ACCEPTABLE
-            {
-                if record.contains(&possible_guess.to_uppercase()) || tmp_path.words.contains(&possible_guess.to_uppercase())
-                {
-                    continue;
-                }
-                let mut new_words = tmp_path.words.clone();
-                new_words.push(possible_guess.to_uppercase());
-                new.push(Path{ words: new_words.clone(), entropy: compute_entropy(&new_words, &possible_answers) });
-                if new.len() > 10
-                {
-                    new.pop();
-                }
-            }
-        }
-        solve = new;
-    }
-
-    let mut info: Vec<(String, f64)> = Vec::new();
-    while let Some(path) = solve.pop()
-    {
-        info.push((path.words.first().unwrap().clone(), path.entropy));
-    }
-    info.reverse();
-    info
-}*/
-
-//测试函数
-pub fn test()
+//测试函数，失败的一局按 max_guesses + 1 步计入，每局的过程按 theme 输出
+pub fn test(max_guesses: usize, theme: Theme)
 {
     let mut count = 0;
     let mut steps = 0;
@@ -328,39 +267,32 @@ pub fn test()
     {
         let answer = Word::new(&answers.to_uppercase());
         println!("{}", answer.origin);
-        let mut record: Vec<String> = Vec::new();
-        let mut full_result: Vec<[u8; 5]> = Vec::new();
-        let mut best_result: HashMap<char, u8> = HashMap::new();
-        let mut is_correct: bool = false;
-        record.push("TARES".to_string());
-        full_result.push(answer.compare("TARES"));
-        (best_result, is_correct) = game::user_update_and_show(&record, &full_result, best_result);
-        if is_correct
-        {
-            count += 1;
-            steps += 1;
-            println!("{} 1 {}", count, steps);
-            continue;
-        }
-        for i in 2..=6
+        let mut session = GameSession::new(answer, &[], Difficulty::Normal, max_guesses);
+        let mut guess = "TARES".to_string();
+        while !session.is_over()
         {
-            let help = solve(&record, &full_result, 6 - i);
-            record.push(help[0].0.clone());
-            full_result.push(answer.compare(&help[0].0));
-            (best_result, is_correct) = game::user_update_and_show(&record, &full_result, best_result);
-            if is_correct
+            if let Err(error) = session.submit_guess(&guess)
             {
-                count += 1;
-                steps += i;
-                println!("{} {} {}", count, i, steps);
+                println!("{}", error);
                 break;
             }
+            game::user_show(&session, theme, &Dictionary::english().keyboard);
+            if !session.is_over()
+            {
+                let help = solve(FeedbackMatrix::builtin(), session.guesses(), session.results(), session.remaining() as i32 - 1);
+                guess = help[0].0.clone();
+            }
         }
-        if !is_correct
+        count += 1;
+        if session.status() == Status::Won
         {
-            count += 1;
-            steps += 7;
-            println!("{} {} {}", count, 7, steps);//失败时认为步数为7
+            steps += session.guesses().len();
+            println!("{} {} {}", count, session.guesses().len(), steps);
+        }
+        else
+        {
+            steps += max_guesses + 1;
+            println!("{} {} {}", count, max_guesses + 1, steps);
         }
     }
-}
\ No newline at end of file
+}
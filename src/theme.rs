@@ -0,0 +1,117 @@
+use std::fmt;
+use std::str::FromStr;
+use crossterm::style::Color;
+use crate::feedback::LetterFeedback;
+
+//配色方案，所有界面（命令行、TUI、分享）共用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme
+{
+    #[default]
+    Default,
+    HighContrast,
+    ColourBlind,
+    Monochrome,
+}
+
+impl Theme
+{
+    pub fn name(&self) -> &'static str
+    {
+        match self
+        {
+            Theme::Default => "default",
+            Theme::HighContrast => "high-contrast",
+            Theme::ColourBlind => "colour-blind",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+
+    //比较结果对应的颜色，单色主题没有颜色
+    pub fn color(&self, feedback: LetterFeedback) -> Option<Color>
+    {
+        match (self, feedback)
+        {
+            (Theme::Monochrome, _) => None,
+            (Theme::ColourBlind, LetterFeedback::Correct) => Some(Color::Rgb{ r: 245, g: 121, b: 58 }),
+            (Theme::ColourBlind, LetterFeedback::Present) => Some(Color::Rgb{ r: 133, g: 192, b: 249 }),
+            (_, LetterFeedback::Correct) => Some(Color::Green),
+            (_, LetterFeedback::Present) => Some(Color::Yellow),
+            (Theme::Default, LetterFeedback::Absent) => Some(Color::Red),
+            (_, LetterFeedback::Absent) => Some(Color::DarkGrey),
+        }
+    }
+
+    //TUI提示中单词的颜色，色盲主题避开红色，单色主题没有颜色
+    pub fn hint(&self) -> Option<Color>
+    {
+        match self
+        {
+            Theme::Monochrome => None,
+            Theme::ColourBlind => self.color(LetterFeedback::Correct),
+            _ => Some(Color::Red),
+        }
+    }
+
+    //高对比度主题使用粗体
+    pub fn bold(&self) -> bool
+    {
+        *self == Theme::HighContrast
+    }
+
+    //单色主题用符号区分结果：[A] 位置正确，(A) 存在，空格包围为不存在
+    pub fn monochrome(&self) -> bool
+    {
+        *self == Theme::Monochrome
+    }
+
+    //猜测中的一个字母，单色主题下为三个字符，尚未猜测的位置为空白
+    pub fn mark(&self, letter: char, feedback: Option<LetterFeedback>) -> String
+    {
+        if !self.monochrome()
+        {
+            return letter.to_string();
+        }
+        match feedback
+        {
+            Some(LetterFeedback::Correct) => format!("[{}]", letter),
+            Some(LetterFeedback::Present) => format!("({})", letter),
+            _ => format!(" {} ", letter),
+        }
+    }
+
+    //键盘上的一个字母，单色主题下确定不存在的字母显示为 -
+    pub fn key_mark(&self, letter: char, feedback: Option<LetterFeedback>) -> String
+    {
+        match feedback
+        {
+            Some(LetterFeedback::Absent) if self.monochrome() => " - ".to_string(),
+            _ => self.mark(letter, feedback),
+        }
+    }
+}
+
+impl fmt::Display for Theme
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Theme
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Theme, String>
+    {
+        match s.to_lowercase().as_str()
+        {
+            "default" => Ok(Theme::Default),
+            "high-contrast" | "high_contrast" => Ok(Theme::HighContrast),
+            "colour-blind" | "color-blind" | "colour_blind" | "color_blind" => Ok(Theme::ColourBlind),
+            "monochrome" | "mono" => Ok(Theme::Monochrome),
+            _ => Err("INVALID THEME".to_string()),
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use regex::{Regex, RegexBuilder};
+use crate::builtin_words::{ACCEPTABLE, FINAL};
+use crate::dictionary::{self, Dictionary, ENGLISH_ALPHABET};
+use crate::error::WordleError;
+use crate::feedback::MAX_LENGTH;
+
+//内置词库的名称，可以代替词库文件使用，其他词典的词库为 NAME:final 与 NAME:acceptable
+pub const BUILTIN_FINAL: &str = "builtin:final";
+pub const BUILTIN_ACCEPTABLE: &str = "builtin:acceptable";
+
+//词库中一行的问题：不合法的字符与过长的单词使词库不可用，其余的行只是被忽略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem
+{
+    BadCharacter(char),
+    TooLong(usize),
+    OtherLength(usize),
+    Duplicate(usize),
+}
+
+impl Problem
+{
+    pub fn is_error(&self) -> bool
+    {
+        matches!(self, Problem::BadCharacter(_) | Problem::TooLong(_))
+    }
+}
+
+impl fmt::Display for Problem
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            Problem::BadCharacter(letter) => write!(f, "INVALID CHARACTER '{}'", letter),
+            Problem::TooLong(length) => write!(f, "{} LETTERS, AT MOST {} ALLOWED", length, MAX_LENGTH),
+            Problem::OtherLength(length) => write!(f, "{} LETTERS, IGNORED", length),
+            Problem::Duplicate(first) => write!(f, "DUPLICATE OF LINE {}", first),
+        }
+    }
+}
+
+//有问题的一行，line 从 1 开始，word 为去掉空白与注释后的内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue
+{
+    pub line: usize,
+    pub word: String,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Issue
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "LINE {}: {}: {}", self.line, self.word, self.problem)
+    }
+}
+
+//读入的词库：words 为长度符合的单词，大写、去重并排序，issues 按行号排列
+//length 为 None 时接受 1 到 MAX_LENGTH 的任意长度
+#[derive(Debug, Clone)]
+pub struct WordList
+{
+    pub path: String,
+    pub length: Option<usize>,
+    pub words: Vec<String>,
+    pub issues: Vec<Issue>,
+}
+
+impl WordList
+{
+    //内置词库，没有问题
+    pub fn builtin(name: &str, words: &[&str]) -> WordList
+    {
+        WordList
+        {
+            path: name.to_string(),
+            length: None,
+            words: words.iter().map(|word| word.to_uppercase()).collect(),
+            issues: Vec::new(),
+        }
+    }
+
+    //每行一个单词，首尾空白被去掉，# 之后为注释，空行被跳过，指定 length 时只保留该长度的单词
+    pub fn parse(path: &str, text: &str, length: Option<usize>) -> WordList
+    {
+        WordList::parse_in(path, text, length, ENGLISH_ALPHABET)
+    }
+
+    //同 parse，单词只能由 alphabet 中的字母组成
+    pub fn parse_in(path: &str, text: &str, length: Option<usize>, alphabet: &str) -> WordList
+    {
+        let mut first: HashMap<String, usize> = HashMap::new();
+        let mut words: Vec<String> = Vec::new();
+        let mut issues: Vec<Issue> = Vec::new();
+        for (index, line) in text.lines().enumerate()
+        {
+            let word = line.split('#').next().unwrap_or_default().trim();
+            if word.is_empty()
+            {
+                continue;
+            }
+            let mut issue = |problem| issues.push(Issue{ line: index + 1, word: word.to_string(), problem });
+            let count = word.chars().count();
+            if let Some(letter) = word.chars().find(|letter| !dictionary::is_letter(alphabet, *letter))
+            {
+                issue(Problem::BadCharacter(letter));
+            }
+            else if count > MAX_LENGTH
+            {
+                issue(Problem::TooLong(count));
+            }
+            else if length.is_some_and(|length| count != length)
+            {
+                issue(Problem::OtherLength(count));
+            }
+            else if let Some(&line) = first.get(&dictionary::uppercase(word))
+            {
+                issue(Problem::Duplicate(line));
+            }
+            else
+            {
+                first.insert(dictionary::uppercase(word), index + 1);
+                words.push(dictionary::uppercase(word));
+            }
+        }
+        words.sort();
+        WordList{ path: path.to_string(), length, words, issues }
+    }
+
+    //读取词库文件，文件无法读取或不是 UTF-8 时返回错误
+    pub fn read(path: &str, length: Option<usize>) -> Result<WordList, WordleError>
+    {
+        WordList::read_in(path, length, ENGLISH_ALPHABET)
+    }
+
+    //同 read，单词只能由 alphabet 中的字母组成
+    pub fn read_in(path: &str, length: Option<usize>, alphabet: &str) -> Result<WordList, WordleError>
+    {
+        let invalid = |line: Option<usize>, error: std::io::Error| WordleError::WordList
+        {
+            path: path.to_string(),
+            line,
+            reason: error.to_string(),
+        };
+        let file = File::open(path).map_err(|error| invalid(None, error))?;
+        let mut text = String::new();
+        for (index, line) in BufReader::new(file).lines().enumerate()
+        {
+            text.push_str(&line.map_err(|error| invalid(Some(index + 1), error))?);
+            text.push('\n');
+        }
+        Ok(WordList::parse_in(path, &text, length, alphabet))
+    }
+
+    //使词库不可用的行
+    pub fn errors(&self) -> impl Iterator<Item = &Issue>
+    {
+        self.issues.iter().filter(|issue| issue.problem.is_error())
+    }
+
+    //内置词库 builtin:final 与 builtin:acceptable，词典的词库 NAME:final 与 NAME:acceptable，或者词库文件
+    pub fn open(name: &str, length: Option<usize>) -> Result<WordList, WordleError>
+    {
+        let list = match name
+        {
+            BUILTIN_FINAL => WordList::builtin(name, FINAL),
+            BUILTIN_ACCEPTABLE => WordList::builtin(name, ACCEPTABLE),
+            _ => match name.split_once(':')
+            {
+                Some((name, set @ ("final" | "acceptable"))) =>
+                {
+                    let dictionary = Dictionary::open(name)?;
+                    let path = if set == "final" { &dictionary.final_set } else { &dictionary.acceptable_set };
+                    match path
+                    {
+                        Some(path) => return WordList::read_in(path, length, &dictionary.alphabet),
+                        None => return WordList::open(&format!("builtin:{}", set), length),
+                    }
+                }
+                _ => return WordList::read(name, length),
+            },
+        };
+        Ok(WordList
+        {
+            words: list.words.into_iter().filter(|word| length.unwrap_or(word.len()) == word.len()).collect(),
+            length,
+            ..list
+        })
+    }
+
+    //词库可以使用：没有不合法的行，且至少有一个长度符合的单词
+    pub fn validate(&self) -> Result<(), WordleError>
+    {
+        let errors: Vec<&Issue> = self.errors().collect();
+        if let Some(issue) = errors.first()
+        {
+            let mut reason = format!("{}: {}", issue.word, issue.problem);
+            if errors.len() > 1
+            {
+                reason.push_str(&format!(" (AND {} MORE BAD LINES)", errors.len() - 1));
+            }
+            return Err(WordleError::WordList{ path: self.path.clone(), line: Some(issue.line), reason });
+        }
+        if self.words.is_empty()
+        {
+            let reason = match self.length
+            {
+                Some(length) => format!("NO WORDS OF LENGTH {}", length),
+                None => "NO WORDS".to_string(),
+            };
+            return Err(WordleError::WordList{ path: self.path.clone(), line: None, reason });
+        }
+        Ok(())
+    }
+}
+
+//words 中不在 remove 中的单词，顺序不变，也用于找出不在候选词库中的答案
+pub fn subtract(words: &[String], remove: &[String]) -> Vec<String>
+{
+    let remove: HashSet<&String> = remove.iter().collect();
+    words.iter().filter(|word| !remove.contains(word)).cloned().collect()
+}
+
+//多个词库的并集，去重并排序
+pub fn merge(lists: &[Vec<String>]) -> Vec<String>
+{
+    let mut words: Vec<String> = lists.iter().flatten().cloned().collect::<HashSet<String>>().into_iter().collect();
+    words.sort();
+    words
+}
+
+//单词的筛选条件，全部满足才保留：正则表达式、逐个位置的字母模式、必须包含与不能包含的字母
+//模式中 ? _ . 表示任意字母，比较时不区分大小写，字母可以是任意字母表中的字母
+#[derive(Debug, Clone, Default)]
+pub struct Filter
+{
+    regex: Option<Regex>,
+    pattern: Option<Vec<Option<char>>>,
+    contains: Vec<char>,
+    excludes: Vec<char>,
+}
+
+impl Filter
+{
+    pub fn new(regex: Option<&str>, pattern: Option<&str>, contains: Option<&str>, excludes: Option<&str>) -> Result<Filter, WordleError>
+    {
+        let regex = match regex
+        {
+            Some(regex) => Some(RegexBuilder::new(regex)
+                .case_insensitive(true)
+                .build()
+                .map_err(|error| WordleError::bad_argument("--regex", &error.to_string()))?),
+            None => None,
+        };
+        let letters = |flag: &str, text: Option<&str>| -> Result<Vec<char>, WordleError>
+        {
+            let text = dictionary::uppercase(text.unwrap_or_default());
+            match text.chars().find(|letter| !letter.is_alphabetic())
+            {
+                Some(letter) => Err(WordleError::bad_argument(flag, &format!("INVALID CHARACTER '{}'", letter))),
+                None => Ok(text.chars().collect()),
+            }
+        };
+        let pattern = match pattern
+        {
+            Some(pattern) => Some(dictionary::uppercase(pattern)
+                .chars()
+                .map(|letter| match letter
+                {
+                    '?' | '_' | '.' => Ok(None),
+                    letter if letter.is_alphabetic() => Ok(Some(letter)),
+                    letter => Err(WordleError::bad_argument("--pattern", &format!("INVALID CHARACTER '{}'", letter))),
+                })
+                .collect::<Result<Vec<Option<char>>, WordleError>>()?),
+            None => None,
+        };
+        Ok(Filter{ regex, pattern, contains: letters("--contains", contains)?, excludes: letters("--excludes", excludes)? })
+    }
+
+    //word 为大写的单词
+    pub fn matches(&self, word: &str) -> bool
+    {
+        if self.regex.as_ref().is_some_and(|regex| !regex.is_match(word))
+        {
+            return false;
+        }
+        if let Some(pattern) = &self.pattern
+        {
+            if word.chars().count() != pattern.len()
+                || word.chars().zip(pattern.iter()).any(|(letter, expected)| expected.is_some_and(|expected| expected != letter))
+            {
+                return false;
+            }
+        }
+        self.contains.iter().all(|letter| word.contains(*letter)) && !self.excludes.iter().any(|letter| word.contains(*letter))
+    }
+}
+
+//一个字母的统计：出现的总次数、包含它的单词数、在每个位置出现的次数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterCount
+{
+    pub letter: char,
+    pub count: usize,
+    pub words: usize,
+    pub positions: Vec<usize>,
+}
+
+//词库的字母频率，letters 按出现次数从多到少排列，没有出现的字母不列出，任意字母表都可以统计
+#[derive(Debug, Clone)]
+pub struct LetterStats
+{
+    pub words: usize,
+    pub letters: Vec<LetterCount>,
+}
+
+impl LetterStats
+{
+    pub fn new(words: &[String]) -> LetterStats
+    {
+        let length = words.iter().map(|word| word.chars().count()).max().unwrap_or(0);
+        let mut letters: Vec<LetterCount> = Vec::new();
+        for word in words
+        {
+            let mut seen: HashSet<char> = HashSet::new();
+            for (position, letter) in word.chars().enumerate()
+            {
+                let index = match letters.iter().position(|entry| entry.letter == letter)
+                {
+                    Some(index) => index,
+                    None =>
+                    {
+                        letters.push(LetterCount{ letter, count: 0, words: 0, positions: vec![0; length] });
+                        letters.len() - 1
+                    }
+                };
+                let entry = &mut letters[index];
+                entry.count += 1;
+                entry.positions[position] += 1;
+                if seen.insert(letter)
+                {
+                    entry.words += 1;
+                }
+            }
+        }
+        letters.sort_by(|a, b| b.count.cmp(&a.count).then(a.letter.cmp(&b.letter)));
+        LetterStats{ words: words.len(), letters }
+    }
+}
+
+//表格形式：每个字母一行，之后为每个位置的次数
+impl fmt::Display for LetterStats
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        writeln!(f, "WORDS {}", self.words)?;
+        write!(f, "{:<8}{:>7}{:>7}", "LETTER", "COUNT", "WORDS")?;
+        for position in 1..=self.letters.first().map_or(0, |entry| entry.positions.len())
+        {
+            write!(f, "{:>6}", position)?;
+        }
+        for entry in self.letters.iter()
+        {
+            write!(f, "\n{:<8}{:>7}{:>7}", entry.letter, entry.count, entry.words)?;
+            for count in entry.positions.iter()
+            {
+                write!(f, "{:>6}", count)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//按读取的格式写出词库，每行一个单词，path 为 None 时写到标准输出
+pub fn write(path: Option<&str>, words: &[String]) -> Result<(), WordleError>
+{
+    let mut text = String::new();
+    for word in words
+    {
+        text.push_str(word);
+        text.push('\n');
+    }
+    match path
+    {
+        Some(path) => std::fs::write(path, text).map_err(|error| WordleError::WordList
+        {
+            path: path.to_string(),
+            line: None,
+            reason: error.to_string(),
+        }),
+        None =>
+        {
+            print!("{}", text);
+            Ok(())
+        }
+    }
+}
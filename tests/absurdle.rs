@@ -0,0 +1,12 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_12_absurdle() {
+    // the answer is picked adversarially and only written to the state once the game is lost
+    TestCase::read("12_01_absurdle").run_and_compare_game_state();
+}
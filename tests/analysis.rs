@@ -0,0 +1,67 @@
+use ntest::timeout;
+use wordle::analysis;
+use wordle::file::Round;
+use wordle::matrix::FeedbackMatrix;
+use wordle::session::DEFAULT_MAX_GUESSES;
+use wordle::{solver, Word};
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+// the first run builds the builtin feedback matrix, which is slow in a debug build
+#[test]
+#[timeout(60000)]
+fn test_14_analysis_table() {
+    // one row per guess comparing the player's entropy with the solver's best single guess
+    TestCase::read("14_01_analysis_table").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_14_analysis_last_game() {
+    // without a game number the last game is analysed, and it never received an answer
+    TestCase::read("14_02_analysis_last_game").run_and_expect_exit();
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn entropy_bits_skill_and_luck() {
+    let answers = words(&["CRANE", "CRATE", "GRACE", "TRACE"]);
+    let matrix = FeedbackMatrix::new(&answers, &answers);
+    let round = Round{ answer: "CRANE".to_string(), guesses: words(&["CRANE"]), answers: Vec::new() };
+    let analysis = analysis::analyze(&round, &answers, DEFAULT_MAX_GUESSES, &matrix).unwrap();
+    assert_eq!(analysis.result, "CORRECT");
+
+    // CRANE splits the four answers into groups of 1, 1 and 2
+    let step = &analysis.steps[0];
+    assert_eq!((step.candidates, step.remaining), (4, 1));
+    assert!((step.entropy - 1.5).abs() < 1e-9);
+    assert!((step.bits - 2.0).abs() < 1e-9);
+    assert!((step.luck - 0.5).abs() < 1e-9);
+    assert!(step.best_entropy >= step.entropy);
+    assert!(step.skill > 0.0 && step.skill <= 100.0);
+
+    let json = serde_json::to_value(&analysis).unwrap();
+    assert_eq!(json["steps"][0]["guess"], "CRANE");
+    assert_eq!(json["steps"][0]["candidates"], 4);
+}
+
+#[test]
+fn a_single_candidate_has_no_negative_zero_entropy() {
+    let answers = words(&["CRANE", "CRATE", "GRACE"]);
+    let matrix = FeedbackMatrix::new(&answers, &answers);
+    let record = words(&["CRANE"]);
+    let results = [Word::new("CRATE").compare("CRANE")];
+    let (info, help) = solver::help(&matrix, &record, &results);
+    assert_eq!(help, [("CRATE".to_string(), 0.0)]);
+    for (word, entropy) in info.iter().chain(help.iter()) {
+        assert_eq!(format!("{:.2}", entropy), "0.00", "{}", word);
+    }
+    for (word, entropy) in solver::solve(&matrix, &record, &results, 2) {
+        assert_eq!(format!("{:.2}", entropy), "0.00", "{}", word);
+    }
+}
@@ -0,0 +1,26 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_15_play_subcommand() {
+    // `play` takes the same flags as the bare command and prints the same output
+    TestCase::read("15_01_play_subcommand").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_15_stats_subcommand() {
+    // statistics of a state file without playing
+    TestCase::read("15_02_stats_subcommand").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_15_invalid_day() {
+    // a day that is not a number is rejected instead of panicking
+    TestCase::read("15_03_invalid_day").run_and_expect_exit();
+}
@@ -7,7 +7,6 @@ use std::process::{Child, Command, Stdio};
 use assert_json_diff::assert_json_matches;
 use lazy_static::lazy_static;
 use pretty_assertions::assert_eq;
-use serde_json;
 
 // Binary targets are automatically built if there is an integration test.
 // This allows an integration test to execute the binary to exercise and test its behavior.
@@ -18,6 +17,25 @@ lazy_static! {
     static ref MBT_DIR: PathBuf = "./wordle-mbt".into();
 }
 
+// the program with the settings of the developer running the tests hidden:
+// no WORDLE_* or NO_COLOR variables, empty config and data directories (so
+// only the builtin dictionary exists) and the feedback matrix cache under target/tmp
+pub fn program() -> Command {
+    let tmp = Path::new(env!("CARGO_TARGET_TMPDIR"));
+    let mut command = Command::new(EXE_PATH.as_os_str());
+    for (name, _) in std::env::vars_os() {
+        if name.to_string_lossy().starts_with("WORDLE_") {
+            command.env_remove(name);
+        }
+    }
+    command
+        .env_remove("NO_COLOR")
+        .env("XDG_CONFIG_HOME", tmp.join("empty-config"))
+        .env("XDG_DATA_HOME", tmp.join("empty-data"))
+        .env("WORDLE_CACHE_DIR", tmp.join("cache"));
+    command
+}
+
 pub struct TestCase {
     name: String,
     arguments: Vec<String>,
@@ -79,11 +97,10 @@ impl TestCase {
             cmd.arg(result_wasm_path).arg("--").env("NO_COLOR", "1");
             cmd
         } else {
-            Command::new(EXE_PATH.as_os_str())
+            program()
         };
         // command options for user program
         let mut command = command
-
             .args(&self.arguments)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
@@ -0,0 +1,88 @@
+use ntest::timeout;
+use wordle::config::{Layers, Source};
+use wordle::file::Config;
+use wordle::WordleError;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_16_toml_config() {
+    // a TOML config file, with --no-stats turning off its `stats = true`
+    TestCase::read("16_01_toml_config").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_16_config_show() {
+    // every setting with the layer it comes from
+    TestCase::read("16_02_config_show").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_16_unknown_key() {
+    // a misspelt key is an error instead of being ignored
+    TestCase::read("16_03_unknown_key").run_and_expect_exit();
+}
+
+#[test]
+fn test_16_layer_precedence() {
+    let mut layers = Layers::default();
+    layers.apply(&wordle::config::defaults(), Source::Default);
+    layers.apply(
+        &Config { random: Some(true), day: Some(5), ..Config::default() },
+        Source::ConfigFile("wordle.toml".into()),
+    );
+    layers
+        .apply_env(|name| match name {
+            "WORDLE_DAY" => Some("7".to_string()),
+            "WORDLE_STATS" => Some("yes".to_string()),
+            _ => None,
+        })
+        .unwrap();
+    layers.apply(&Config { random: Some(false), ..Config::default() }, Source::CommandLine);
+
+    let config = layers.config().unwrap();
+    assert_eq!(config.random, Some(false));
+    assert_eq!(config.day, Some(7));
+    assert_eq!(config.stats, Some(true));
+    assert_eq!(config.boards, Some(1));
+    assert_eq!(layers.get("random").unwrap().1, Source::CommandLine);
+    assert_eq!(layers.get("day").unwrap().1, Source::Env("WORDLE_DAY".to_string()));
+    assert_eq!(layers.get("boards").unwrap().1, Source::Default);
+    assert!(layers.get("word").is_none());
+}
+
+#[test]
+fn test_16_invalid_env() {
+    let mut layers = Layers::default();
+    let error = layers.apply_env(|name| (name == "WORDLE_SEED").then(|| "abc".to_string())).unwrap_err();
+    assert!(matches!(&error, WordleError::BadArgument { flag, .. } if flag == "WORDLE_SEED"));
+    assert_eq!(error.exit_code(), 2);
+    // NO_COLOR is not a WORDLE_ variable
+    layers.apply_env(|name| (name == "NO_COLOR").then(|| "1".to_string())).unwrap();
+    assert!(layers.get("theme").is_none());
+}
+
+#[test]
+fn test_16_no_color_only_replaces_the_default_theme() {
+    let no_color = |name: &str| (name == "NO_COLOR").then(|| "1".to_string());
+    let mut layers = Layers::default();
+    layers.apply(&wordle::config::defaults(), Source::Default);
+    layers.apply_no_color(no_color);
+    assert_eq!(layers.config().unwrap().theme.as_deref(), Some("monochrome"));
+    assert_eq!(layers.get("theme").unwrap().1, Source::Env("NO_COLOR".to_string()));
+
+    // a theme chosen in a config file wins over NO_COLOR
+    layers.apply(&Config { theme: Some("high-contrast".to_string()), ..Config::default() }, Source::UserFile("config.toml".into()));
+    assert_eq!(layers.config().unwrap().theme.as_deref(), Some("high-contrast"));
+
+    // an empty NO_COLOR is ignored
+    let mut layers = Layers::default();
+    layers.apply(&wordle::config::defaults(), Source::Default);
+    layers.apply_no_color(|name| (name == "NO_COLOR").then(String::new));
+    assert_eq!(layers.get("theme").unwrap().1, Source::Default);
+}
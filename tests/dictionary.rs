@@ -0,0 +1,191 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use crossterm::event::KeyCode;
+use tui::backend::TestBackend;
+use tui::layout::Rect;
+use tui::Terminal;
+use wordle::dictionary::{self, Dictionary, QWERTY};
+use wordle::screen::{self, Screen};
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::wordlist::{Problem, WordList};
+use wordle::{Difficulty, LetterFeedback, Word, WordleError};
+
+#[allow(dead_code)]
+mod common;
+
+// runs the binary with the dictionaries in tests/data/dictionaries
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = common::program()
+        .args(args)
+        .env("WORDLE_DATA_DIR", "tests/data")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn german() -> Dictionary {
+    Dictionary::load("de", Path::new("tests/data/dictionaries/de")).unwrap()
+}
+
+#[test]
+fn test_18_german_game() {
+    // the keyboard in test mode follows the 30-letter alphabet, ß included
+    let output = run(&["--dictionary", "de", "-w", "grüße"], "äpfel\nKÜCHE\nhello\ngrüße\n");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "RRRYR XXXXYRXXXXXRXXXRXXXXXXXXXXRXXX\n\
+         RYRRG XXRXGRXRXXRRXXXRXXXXXXXXXXRXYX\n\
+         INVALID\n\
+         GGGGG XXRXGRGRXXRRXXXRXGXXXXXXXXRXGG\n\
+         CORRECT 3\n"
+    );
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_18_jargon_without_acceptable_list() {
+    // a dictionary with only final.txt accepts its answers as guesses, in any length
+    let output = run(&["--dictionary", "jargon", "-l", "6", "-w", "socket"], "kernel\nsocket\n");
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "YRRRGR XXXXGXXXXXYRXRXXXRXXXXXXXX\nGGGGGG XXGXGXXXXXGRXRGXXRGGXXXXXX\nCORRECT 2\n"
+    );
+}
+
+#[test]
+fn test_18_dictionary_commands() {
+    let output = run(&["dictionary", "list"], "");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "en: English (builtin)\nde: Deutsch\njargon\n");
+    // without WORDLE_DATA_DIR the tests see no user dictionaries, whatever the developer has installed
+    let output = common::program().args(["dictionary", "list"]).output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "en: English (builtin)\n");
+
+    let output = run(&["dictionary", "show", "de"], "");
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("ALPHABET ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜß (30 LETTERS)"), "{}", text);
+    assert!(text.ends_with("KEYBOARD\nQ W E R T Z U I O P Ü\n A S D F G H J K L Ö Ä\n  Y X C V B N M ß\n"), "{}", text);
+
+    // the lists of a dictionary can be used by the word list tools
+    let output = run(&["wordlist", "filter", "de:acceptable", "--pattern", "?ü???"], "");
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "KÜCHE\nTÜREN\n");
+    let output = run(&["wordlist", "check", "--dictionary", "de"], "");
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_18_unknown_dictionary() {
+    for name in ["fr", "../de"] {
+        let output = run(&["--dictionary", name, "-w", "crane"], "");
+        assert_eq!(output.status.code(), Some(2), "{}", name);
+    }
+    // WORDLE_DICTIONARY is a config key like the others
+    let output = common::program()
+        .args(["config", "show"])
+        .env("WORDLE_DICTIONARY", "de")
+        .output()
+        .unwrap();
+    assert!(String::from_utf8(output.stdout).unwrap().contains("dictionary = \"de\"  # $WORDLE_DICTIONARY"));
+}
+
+#[test]
+fn test_18_non_ascii_words_compare() {
+    let answer = Word::new("GRÜßE");
+    assert_eq!(answer.length(), 5);
+    assert_eq!(answer.compare("grüße").to_string(), "GGGGG");
+    assert_eq!(answer.compare("ÜBERS").to_string(), "YRYYR");
+
+    let words: Vec<String> = ["ÄPFEL", "GRÜßE"].iter().map(|word| word.to_string()).collect();
+    let mut session = MultiSession::new(vec![answer], &words, Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    session.submit_guess("äpfel").unwrap();
+    assert_eq!(session.boards()[0].keyboard().get(&'Ä'), Some(&LetterFeedback::Absent));
+}
+
+#[test]
+fn test_18_word_lists_follow_the_alphabet() {
+    let german = german();
+    let list = WordList::parse_in("de", "äpfel\ngrüße\ncafés\n", Some(5), &german.alphabet);
+    assert_eq!(list.words, ["GRÜßE", "ÄPFEL"]);
+    assert_eq!(list.issues[0].problem, Problem::BadCharacter('é'));
+
+    // the same words are not English
+    let list = WordList::parse("en", "äpfel\n", Some(5));
+    assert_eq!(list.issues[0].problem, Problem::BadCharacter('ä'));
+    assert_eq!(dictionary::uppercase("straße"), "STRAßE");
+}
+
+#[test]
+fn test_18_invalid_dictionaries() {
+    let dir = std::env::temp_dir().join(format!("wordle-dictionary-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let load = |manifest: &str| {
+        std::fs::write(dir.join("dictionary.toml"), manifest).unwrap();
+        Dictionary::load("test", &dir)
+    };
+
+    // final.txt is required
+    assert!(matches!(load(""), Err(WordleError::WordList { .. })));
+    std::fs::write(dir.join("final.txt"), "abc\n").unwrap();
+
+    // without a keyboard the alphabet is split into rows
+    let dictionary = load("alphabet = \"abcdefg\"").unwrap();
+    assert_eq!(dictionary.keyboard, ["A B C", "D E F", "G"]);
+    assert_eq!(dictionary.acceptable_set, dictionary.final_set);
+    assert_eq!(load("").unwrap().keyboard, QWERTY);
+
+    for (manifest, reason) in [
+        ("alphabet = \"ab1\"", "INVALID LETTER '1' IN ALPHABET"),
+        ("alphabet = \"abca\"", "LETTER A REPEATED IN ALPHABET"),
+        ("alphabet = \"abc\"\nkeyboard = [\"a b\"]", "LETTER C IS MISSING FROM THE KEYBOARD"),
+        ("alphabet = \"abc\"\nkeyboard = [\"a b c d\"]", "KEY D IS NOT IN THE ALPHABET"),
+        ("alphabet = \"abc\"\nkeyboard = [\"a\", \"b\", \"c\", \"\"]", "KEYBOARD MUST HAVE 1 TO 3 ROWS"),
+    ] {
+        match load(manifest) {
+            Err(error @ WordleError::Config { .. }) => assert!(error.to_string().ends_with(reason), "{}", error),
+            other => panic!("unexpected {:?} for {}", other, manifest),
+        }
+    }
+    assert!(matches!(load("colour = true"), Err(WordleError::Config { .. })));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_18_tui_keyboard_layout() {
+    let mut screen = Screen::new();
+    screen.layout = german().keyboard;
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+    terminal.draw(|f| screen.draw(f, "")).unwrap();
+    let area = terminal.backend().buffer().area;
+    let clickable: Vec<char> = (0..area.width)
+        .flat_map(|x| (0..area.height).map(move |y| (x, y)))
+        .filter_map(|(x, y)| match screen.key_at(area, x, y) {
+            Some(KeyCode::Char(letter)) => Some(letter),
+            _ => None,
+        })
+        .collect();
+    for letter in german().alphabet.chars() {
+        assert!(clickable.contains(&letter), "{} is on the keyboard", letter);
+    }
+    assert!(!clickable.contains(&'Ñ'));
+
+    // the widest row of the German layout is wider than QWERTY's
+    let widest = |keys: Vec<(KeyCode, Rect)>| keys.iter().map(|(_, key)| key.right()).max().unwrap();
+    assert!(widest(screen::layout_keys(Rect::new(0, 0, 100, 3), &screen.layout)) > widest(screen::keyboard_keys(Rect::new(0, 0, 100, 3))));
+}
+
+#[test]
+fn test_18_tui_tiles_are_uppercase() {
+    let mut screen = Screen::new();
+    screen.layout = german().keyboard;
+    screen.show(&MultiSession::new(vec![Word::new("GRÜßE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES));
+    let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+    terminal.draw(|f| screen.draw(f, "äpfß")).unwrap();
+    let buffer = terminal.backend().buffer();
+    let row: String = (0..buffer.area.width).map(|x| buffer.get(x, 3).symbol.clone()).collect();
+    // ß has no single uppercase letter and stays as it is
+    assert!(row.contains("Ä ││ P ││ F ││ ß"), "{}", row);
+}
@@ -0,0 +1,61 @@
+use std::process::Stdio;
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::wordlist::WordList;
+use wordle::{file, Difficulty, Word, WordleError};
+
+#[allow(dead_code)]
+mod common;
+
+fn exit_code(args: &[&str]) -> Option<i32> {
+    common::program()
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .unwrap()
+        .code()
+}
+
+#[test]
+fn state_errors_carry_the_json_position() {
+    let error = file::read_state("tests/cases/07_03_invalid_json_format.before.json").unwrap_err();
+    match &error {
+        WordleError::State { path, position, .. } => {
+            assert_eq!(path, "tests/cases/07_03_invalid_json_format.before.json");
+            assert_eq!(*position, Some((2, 28)));
+        }
+        _ => panic!("unexpected error {:?}", error),
+    }
+    assert_eq!(error.exit_code(), 5);
+}
+
+#[test]
+fn word_list_errors_carry_the_line_number() {
+    let path = std::env::temp_dir().join(format!("wordle-error-{}.txt", std::process::id()));
+    std::fs::write(&path, b"crane\nslate\n\xff\xfe\n").unwrap();
+    let error = WordList::read(path.to_str().unwrap(), Some(5)).unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(error, WordleError::WordList { line: Some(3), .. }), "{:?}", error);
+    assert_eq!(error.exit_code(), 4);
+}
+
+#[test]
+fn invalid_guesses_keep_their_reason() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Hard, DEFAULT_MAX_GUESSES);
+    session.submit_guess("TEARS").unwrap();
+    let error = session.submit_guess("bland").unwrap_err();
+    assert!(matches!(&error, WordleError::InvalidGuess { guess, .. } if guess == "BLAND"));
+    assert_eq!(error.to_string(), "GUESS MUST CONTAIN E");
+
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    assert_eq!(session.submit_guess("XXXXX").unwrap_err().to_string(), "INVALID");
+}
+
+#[test]
+fn each_kind_of_error_has_its_own_exit_code() {
+    assert_eq!(exit_code(&["-w", "XXXXX"]), Some(2));
+    assert_eq!(exit_code(&["-c", "tests/cases/16_03_unknown_key.config.toml"]), Some(3));
+    assert_eq!(exit_code(&["-f", "tests/data/no_such_file.txt"]), Some(4));
+    assert_eq!(exit_code(&["-S", "tests/cases/07_03_invalid_json_format.before.json"]), Some(5));
+}
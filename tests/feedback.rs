@@ -0,0 +1,47 @@
+use std::str::FromStr;
+use wordle::feedback::{LetterFeedback, MAX_LENGTH};
+use wordle::Pattern;
+
+#[test]
+fn patterns_pack_letters_in_base_3() {
+    use LetterFeedback::*;
+    // the first letter is the lowest digit: R = 0, Y = 1, G = 2
+    let pattern = Pattern::new(&[Correct, Present, Absent, Absent, Absent]);
+    assert_eq!(pattern.code(), 2 + 3);
+    assert_eq!(pattern.length(), 5);
+    assert_eq!(pattern.letters().collect::<Vec<_>>(), [Correct, Present, Absent, Absent, Absent]);
+    assert_eq!(Pattern::correct(5).code() as usize, Pattern::count(5) - 1);
+    assert!(Pattern::correct(5).is_correct());
+    assert!(!pattern.is_correct());
+
+    // every code round-trips through its letters
+    for code in 0..Pattern::count(4) as u16 {
+        let pattern = Pattern::from_code(code, 4).unwrap();
+        assert_eq!(Pattern::new(&pattern.letters().collect::<Vec<_>>()), pattern);
+    }
+    assert_eq!(Pattern::from_code(Pattern::count(4) as u16, 4), None);
+    assert_eq!(Pattern::from_code(0, MAX_LENGTH + 1), None);
+    assert_eq!(Pattern::count(MAX_LENGTH) - 1, Pattern::correct(MAX_LENGTH).code() as usize);
+}
+
+#[test]
+fn patterns_parse_and_print_as_letters() {
+    let pattern = Pattern::from_str(" gyRRg ").unwrap();
+    assert_eq!(pattern.to_string(), "GYRRG");
+    assert_eq!(pattern.length(), 5);
+    assert_eq!("RRRRRRRR".parse::<Pattern>().unwrap().length(), MAX_LENGTH);
+    for invalid in ["", "GYRXG", "RRRRRRRRR", "G Y"] {
+        assert!(invalid.parse::<Pattern>().is_err(), "{:?}", invalid);
+    }
+    // equal letters with different lengths are different patterns
+    assert_ne!("RRRR".parse::<Pattern>().unwrap(), "RRRRR".parse::<Pattern>().unwrap());
+}
+
+#[test]
+fn patterns_are_saved_as_strings() {
+    let pattern: Pattern = "YGRRG".parse().unwrap();
+    assert_eq!(serde_json::to_string(&pattern).unwrap(), "\"YGRRG\"");
+    assert_eq!(serde_json::from_str::<Pattern>("\"ygrrg\"").unwrap(), pattern);
+    assert!(serde_json::from_str::<Pattern>("\"YGRXG\"").is_err());
+    assert!(serde_json::from_str::<Pattern>("12").is_err());
+}
@@ -0,0 +1,54 @@
+use wordle::{HardModeRules, Pattern, Word};
+
+fn rules_after(strict: bool, answer: &str, guesses: &[&str]) -> HardModeRules {
+    let answer = Word::new(answer);
+    let mut rules = HardModeRules::new(strict);
+    for guess in guesses {
+        rules.record(guess, answer.compare(guess));
+    }
+    rules
+}
+
+#[test]
+fn greens_from_every_guess_are_kept() {
+    // the green A from the first guess still applies after a later guess
+    let rules = rules_after(false, "CRANE", &["BLAST", "GRAND"]);
+    assert_eq!(rules.check("BRAND"), Ok(()));
+    assert_eq!(rules.check("BRINE"), Err("3RD LETTER MUST BE A".to_string()));
+}
+
+#[test]
+fn yellow_multiplicity_is_enforced() {
+    // two yellow E's need two E's in the next guess
+    let rules = rules_after(false, "EERIE", &["SPEED"]);
+    assert_eq!(rules.check("ERROR"), Err("GUESS MUST CONTAIN 2 E".to_string()));
+    assert_eq!(rules.check("EERIE"), Ok(()));
+}
+
+#[test]
+fn normal_level_allows_grey_letters_and_yellow_positions() {
+    let rules = rules_after(false, "CRANE", &["TEARS"]);
+    assert_eq!(rules.check("TEARY"), Ok(()));
+}
+
+#[test]
+fn strict_level_bans_grey_letters_and_yellow_positions() {
+    let rules = rules_after(true, "CRANE", &["TEARS"]);
+    assert_eq!(rules.check("TRADE"), Err("GUESS MUST NOT CONTAIN T".to_string()));
+    assert_eq!(rules.check("REARM"), Err("2ND LETTER CANNOT BE E".to_string()));
+    assert_eq!(rules.check("CRANE"), Ok(()));
+}
+
+#[test]
+fn strict_level_caps_letters_with_known_count() {
+    // one E is green and the others grey, so the answer has exactly one E
+    let rules = rules_after(true, "CRANE", &["EERIE"]);
+    assert_eq!(rules.check("REEVE"), Err("GUESS MUST CONTAIN AT MOST 1 E".to_string()));
+}
+
+#[test]
+fn correct_pattern_records_every_position() {
+    let mut rules = HardModeRules::new(false);
+    rules.record("CRANE", Pattern::correct(5));
+    assert_eq!(rules.check("CRANK"), Err("5TH LETTER MUST BE E".to_string()));
+}
@@ -0,0 +1,59 @@
+use std::io::Cursor;
+use wordle::input::{self, ScriptedInput, StreamInput};
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::{Difficulty, Status, Word, WordleError};
+
+fn script(lines: &[&str]) -> ScriptedInput<std::vec::IntoIter<String>> {
+    ScriptedInput::new(lines.iter().map(|line| line.to_string()).collect::<Vec<String>>())
+}
+
+fn session(answer: &str, difficulty: Difficulty) -> MultiSession {
+    MultiSession::new(vec![Word::new(answer)], &[], difficulty, DEFAULT_MAX_GUESSES)
+}
+
+#[test]
+fn invalid_guesses_are_reported_and_skipped() {
+    let mut input = script(&["XXXXX", "CRANE"]);
+    let mut session = session("CRANE", Difficulty::Normal);
+    assert_eq!(input::read_guess(&mut input, &mut session).unwrap(), Some(Status::Won));
+    assert_eq!(input.warnings, vec!["INVALID".to_string()]);
+}
+
+#[test]
+fn end_of_input_stops_reading() {
+    let mut input = script(&["XXXXX"]);
+    let mut session = session("CRANE", Difficulty::Normal);
+    assert_eq!(input::read_guess(&mut input, &mut session).unwrap(), None);
+    assert_eq!(input::read_answer(&mut input, &[], 5).unwrap(), None);
+    assert!(!input::read_yes_no(&mut input, "").unwrap());
+}
+
+#[test]
+fn hard_mode_reasons_reach_every_source() {
+    let mut input = script(&["TEARS", "BLAND", "CRANE"]);
+    let mut session = session("CRANE", Difficulty::Hard);
+    assert_eq!(input::read_guess(&mut input, &mut session).unwrap(), Some(Status::Playing));
+    assert_eq!(input::read_guess(&mut input, &mut session).unwrap(), Some(Status::Won));
+    assert_eq!(input.warnings, vec!["GUESS MUST CONTAIN E".to_string()]);
+}
+
+#[test]
+fn answers_and_yes_no_share_validation() {
+    let mut input = script(&["ZZZZZ", "crane", "maybe", "y"]);
+    let answer = input::read_answer(&mut input, &[], 5).unwrap().unwrap();
+    assert_eq!(answer.origin, "CRANE");
+    assert!(matches!(input::read_yes_no(&mut input, ""), Err(WordleError::InvalidInput(_))));
+    assert!(input::read_yes_no(&mut input, "").unwrap());
+    assert_eq!(input.warnings, vec!["INVALID".to_string()]);
+}
+
+#[test]
+fn streams_echo_prompts_and_warnings() {
+    let mut output: Vec<u8> = Vec::new();
+    {
+        let mut input = StreamInput::new(Cursor::new("ABCDE\nCRANE\n"), &mut output);
+        let mut session = session("CRANE", Difficulty::Normal);
+        assert_eq!(input::read_guess(&mut input, &mut session).unwrap(), Some(Status::Won));
+    }
+    assert_eq!(String::from_utf8(output).unwrap(), "CHOOSE A GUESS:\nINVALID\nCHOOSE A GUESS:\n");
+}
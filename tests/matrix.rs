@@ -0,0 +1,126 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use wordle::matrix::{self, FeedbackMatrix};
+
+// every test points the cache at the same directory under target/tmp and uses its own word lists
+fn cache_dir() -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("matrix");
+    std::env::set_var("WORDLE_CACHE_DIR", &dir);
+    dir
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|word| word.to_string()).collect()
+}
+
+fn same(a: &FeedbackMatrix, b: &FeedbackMatrix) -> bool {
+    a.guesses() == b.guesses() && a.answers() == b.answers() && a.length() == b.length()
+        && (0..a.guesses().len()).all(|guess| a.row(guess) == b.row(guess))
+}
+
+// builds the cache for the lists and returns its path
+fn cached(guesses: &[String], answers: &[String]) -> PathBuf {
+    let path = cache_dir().join(matrix::cache_name(guesses, answers));
+    let _ = fs::remove_file(&path);
+    let matrix = FeedbackMatrix::cached(guesses, answers);
+    assert!(path.is_file());
+    assert!(same(&matrix, &FeedbackMatrix::new(guesses, answers)));
+    path
+}
+
+fn invalid_data(result: std::io::Result<FeedbackMatrix>, reason: &str) {
+    match result {
+        Err(error) => {
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+            assert_eq!(error.to_string(), reason);
+        }
+        Ok(_) => panic!("expected {}", reason),
+    }
+}
+
+#[test]
+fn cache_round_trip() {
+    let guesses = words(&["CRANE", "SLATE", "ABBEY", "EERIE"]);
+    let answers = words(&["CRATE", "KEBAB"]);
+    let path = cached(&guesses, &answers);
+    let loaded = FeedbackMatrix::load(&path, &guesses, &answers).unwrap();
+    assert!(same(&loaded, &FeedbackMatrix::new(&guesses, &answers)));
+    assert_eq!(loaded.get(0, 0).to_string(), "GGGRG");
+    assert_eq!(fs::metadata(&path).unwrap().len(), 28 + 4 * 2 * 2);
+
+    // a second call reads the file instead of rebuilding it
+    let modified = fs::metadata(&path).unwrap().modified().unwrap();
+    assert!(same(&FeedbackMatrix::cached(&guesses, &answers), &loaded));
+    assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cache_version_mismatch() {
+    let guesses = words(&["ROBOT", "TOAST"]);
+    let answers = words(&["BOOST"]);
+    let path = cached(&guesses, &answers);
+    let mut bytes = fs::read(&path).unwrap();
+    bytes[4..8].copy_from_slice(&1u32.to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+    invalid_data(FeedbackMatrix::load(&path, &guesses, &answers), "UNSUPPORTED VERSION");
+
+    // the stale file is rebuilt and written back
+    let matrix = FeedbackMatrix::load_or_build(&guesses, &answers, Some(&path));
+    assert!(same(&matrix, &FeedbackMatrix::new(&guesses, &answers)));
+    assert!(FeedbackMatrix::load(&path, &guesses, &answers).is_ok());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cache_word_list_mismatch() {
+    let guesses = words(&["LEMON", "MELON"]);
+    let answers = words(&["MELON", "LEMON"]);
+    let path = cached(&guesses, &answers);
+    // the same words in another order are another matrix
+    let reversed = words(&["MELON", "LEMON"]);
+    invalid_data(FeedbackMatrix::load(&path, &reversed, &answers), "WORD LIST MISMATCH");
+    assert_ne!(matrix::cache_name(&guesses, &answers), matrix::cache_name(&reversed, &answers));
+
+    // the hash keeps the guesses apart from the answers
+    assert_ne!(
+        matrix::word_list_hash(&words(&["LEMON"]), &words(&["MELON"])),
+        matrix::word_list_hash(&words(&["LEMON", "MELON"]), &[])
+    );
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cache_truncated_file() {
+    let guesses = words(&["PIZZA", "PASTA", "SALSA"]);
+    let answers = words(&["PIZZA", "SALSA"]);
+    let path = cached(&guesses, &answers);
+    let bytes = fs::read(&path).unwrap();
+    for length in [0, 20, bytes.len() - 1] {
+        fs::write(&path, &bytes[..length]).unwrap();
+        let error = FeedbackMatrix::load(&path, &guesses, &answers).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::UnexpectedEof, "{} bytes", length);
+    }
+    let matrix = FeedbackMatrix::load_or_build(&guesses, &answers, Some(&path));
+    assert!(same(&matrix, &FeedbackMatrix::new(&guesses, &answers)));
+    assert_eq!(fs::read(&path).unwrap(), bytes);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cache_corrupt_patterns() {
+    let guesses = words(&["QUEEN", "QUEUE"]);
+    let answers = words(&["QUEUE"]);
+    let path = cached(&guesses, &answers);
+    let mut bytes = fs::read(&path).unwrap();
+    // 3^5 = 243 is the first code that is not a pattern
+    bytes[30..32].copy_from_slice(&243u16.to_le_bytes());
+    fs::write(&path, &bytes).unwrap();
+    invalid_data(FeedbackMatrix::load(&path, &guesses, &answers), "INVALID PATTERN");
+
+    bytes[0] = b'X';
+    fs::write(&path, &bytes).unwrap();
+    invalid_data(FeedbackMatrix::load(&path, &guesses, &answers), "NOT A FEEDBACK MATRIX");
+    fs::remove_file(&path).unwrap();
+}
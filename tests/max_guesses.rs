@@ -0,0 +1,12 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_10_max_guesses() {
+    // the game fails after three guesses instead of six
+    TestCase::read("10_01_max_guesses").run_and_compare_result();
+}
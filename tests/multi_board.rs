@@ -0,0 +1,12 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_11_multi_board() {
+    // two boards drawn from the seeded shuffle, the round records both answers
+    TestCase::read("11_01_multi_board").run_and_compare_game_state();
+}
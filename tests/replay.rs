@@ -0,0 +1,49 @@
+use ntest::timeout;
+use wordle::file::Round;
+use wordle::matrix::FeedbackMatrix;
+use wordle::replay;
+use wordle::session::DEFAULT_MAX_GUESSES;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_13_replay_list() {
+    // every saved game is listed, unfinished ones without their answer
+    TestCase::read("13_01_replay_list").run_and_compare_result();
+}
+
+// the first run builds the builtin feedback matrix, which is slow in a debug build
+#[test]
+#[timeout(60000)]
+fn test_13_replay_game() {
+    // feedback for both boards, remaining candidates and the solver's pick before each guess
+    TestCase::read("13_02_replay_game").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_13_replay_unfinished() {
+    // an absurdle round that never ended has no answer to replay against
+    TestCase::read("13_03_replay_unfinished").run_and_expect_exit();
+}
+
+#[test]
+fn replay_recomputes_feedback_and_candidates() {
+    let guesses: Vec<String> = vec!["TRACE".to_string(), "CRANE".to_string()];
+    let matrix = FeedbackMatrix::new(&guesses, &["CRANE".to_string(), "CRATE".to_string(), "GRACE".to_string()]);
+    let round = Round{ answer: "CRANE".to_string(), guesses: guesses.clone(), answers: Vec::new() };
+    let steps = replay::replay(&round, &guesses, DEFAULT_MAX_GUESSES, &matrix).unwrap();
+    assert_eq!(steps.len(), 2);
+    assert_eq!(steps[0].guess(), "TRACE");
+    assert_eq!(steps[0].session.boards()[0].results()[0].to_string(), "RGGYG");
+    assert_eq!(steps[0].remaining, vec![1]);
+    assert_eq!(steps[1].remaining, vec![1]);
+    assert!(steps[1].session.is_over());
+    assert!(steps[0].suggestion.is_some());
+
+    let unfinished = Round{ answer: String::new(), guesses, answers: Vec::new() };
+    assert!(replay::replay(&unfinished, &[], DEFAULT_MAX_GUESSES, &matrix).is_err());
+}
@@ -0,0 +1,325 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tui::backend::TestBackend;
+use crossterm::event::KeyCode;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Color;
+use tui::Terminal;
+use wordle::screen::{self, Screen, TerminalGuard};
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::{Difficulty, Word};
+
+fn render(screen: &Screen, input: &str) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(100, 34)).unwrap();
+    terminal.draw(|f| screen.draw(f, input)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn find(buffer: &Buffer, symbol: &str) -> Vec<(u16, u16)> {
+    let area = buffer.area;
+    (area.y..area.bottom())
+        .flat_map(|y| (area.x..area.right()).map(move |x| (x, y)))
+        .filter(|&(x, y)| buffer.get(x, y).symbol == symbol)
+        .collect()
+}
+
+#[test]
+fn guesses_are_drawn_as_coloured_tiles() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    session.submit_guess("SLOTH").unwrap();
+    session.submit_guess("CRAMP").unwrap();
+    let mut screen = Screen::new();
+    screen.show(&session);
+    let buffer = render(&screen, "BR");
+
+    // the green C of CRAMP sits in a tile on the second row
+    let tiles: Vec<_> = find(&buffer, "C").into_iter().filter(|&(x, y)| buffer.get(x, y).bg == Color::Green).collect();
+    assert_eq!(tiles.len(), 2, "the tile and the keyboard key are both green");
+    // the letters being typed appear in the third row without a colour
+    let (bx, by) = find(&buffer, "B").into_iter().min_by_key(|&(x, _)| x).unwrap();
+    assert_eq!(buffer.get(bx, by).bg, Color::Reset);
+    let (cx, cy) = tiles.iter().copied().min_by_key(|&(_, y)| y).unwrap();
+    assert_eq!((bx, by), (cx, cy + 3));
+}
+
+#[test]
+fn small_terminals_do_not_panic() {
+    let session = MultiSession::new(vec![Word::new("CRANE"); 8], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    let mut screen = Screen::new();
+    screen.show(&session);
+    for (width, height) in [(100, 34), (40, 12), (10, 5), (1, 1)] {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| screen.draw(f, "ABCDE")).unwrap();
+    }
+}
+
+#[test]
+fn every_key_is_clickable_where_it_is_drawn() {
+    let screen = Screen::new();
+    let buffer = render(&screen, "");
+    for letter in 'A'..='Z' {
+        let positions: Vec<_> = find(&buffer, &letter.to_string())
+            .into_iter()
+            .filter(|&(x, y)| screen.key_at(buffer.area, x, y) == Some(KeyCode::Char(letter)))
+            .collect();
+        assert_eq!(positions.len(), 1, "{} is drawn once on the keyboard", letter);
+    }
+    let (x, y) = find(&buffer, "K").into_iter().find(|&(x, y)| screen.key_at(buffer.area, x, y) == Some(KeyCode::Backspace)).unwrap();
+    assert_eq!(buffer.get(x - 1, y).symbol, "B");
+    let enter: Vec<_> = find(&buffer, "E").into_iter().filter(|&(x, y)| screen.key_at(buffer.area, x, y) == Some(KeyCode::Enter)).collect();
+    assert_eq!(enter.len(), 2);
+}
+
+#[test]
+fn keys_do_not_overlap() {
+    let keys = screen::keyboard_keys(Rect::new(0, 0, 60, 3));
+    assert_eq!(keys.len(), 28);
+    for (i, (_, a)) in keys.iter().enumerate() {
+        for (_, b) in keys.iter().skip(i + 1) {
+            assert!(!a.intersects(*b), "{:?} overlaps {:?}", a, b);
+        }
+    }
+    // keys that do not fit are left out rather than drawn outside the panel
+    assert!(screen::keyboard_keys(Rect::new(0, 0, 10, 2)).iter().all(|(_, key)| key.right() <= 10 && key.bottom() <= 2));
+}
+
+fn text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    (area.y..area.bottom())
+        .map(|y| (area.x..area.right()).map(|x| buffer.get(x, y).symbol.clone()).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn render_at(screen: &Screen, width: u16, height: u16) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| screen.draw(f, "")).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+#[test]
+fn tiny_terminals_show_a_warning_instead_of_the_game() {
+    let screen = Screen::new();
+    let buffer = render_at(&screen, 30, 10);
+    assert!(text(&buffer).contains("TERMINAL TOO SMALL"));
+    assert_eq!(screen.key_at(buffer.area, 5, 5), None);
+    assert!(!text(&render_at(&screen, 80, 24)).contains("TERMINAL TOO SMALL"));
+}
+
+#[test]
+fn hints_move_below_the_grid_on_narrow_terminals() {
+    let mut screen = Screen::new();
+    screen.show(&MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES));
+    screen.side_title = "HINTS".to_string();
+    screen.side = vec!["SLATE 5.87".into()];
+    let wide = render_at(&screen, 100, 34);
+    let narrow = render_at(&screen, 50, 40);
+    let (wx, wy) = find(&wide, "S").into_iter().find(|&(x, y)| wide.get(x + 1, y).symbol == "L").unwrap();
+    let (nx, ny) = find(&narrow, "S").into_iter().find(|&(x, y)| narrow.get(x + 1, y).symbol == "L").unwrap();
+    assert!(wx > 60 && wy < 5, "beside the grid on wide terminals");
+    assert!(nx < 5 && ny > 20, "below the grid on narrow terminals");
+}
+
+#[test]
+fn long_multi_board_games_scroll_to_the_current_row() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE"), Word::new("SLOTH")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    for guess in ["ABBEY", "BLOCK", "CHIRP", "DOUGH", "EIGHT"] {
+        session.submit_guess(guess).unwrap();
+    }
+    let mut screen = Screen::new();
+    screen.show(&session);
+    let all = text(&render_at(&screen, 80, 24));
+    assert!(all.contains("ROWS 4-6 OF 7"), "{}", all);
+    assert!(all.contains("E ││ I ││ G ││ H ││ T"), "{}", all);
+    assert!(!all.contains("A ││ B ││ B ││ E ││ Y"), "{}", all);
+
+    // everything fits on a taller terminal
+    let all = text(&render_at(&screen, 80, 40));
+    assert!(!all.contains("ROWS"), "{}", all);
+    assert!(all.contains("A ││ B ││ B ││ E ││ Y"), "{}", all);
+}
+
+// letters drawn inside a tile of the grid, with their background
+fn tiles(buffer: &Buffer, symbol: &str) -> Vec<(u16, u16, Color)> {
+    find(buffer, symbol)
+        .into_iter()
+        .filter(|&(x, y)| x >= 2 && y >= 1 && buffer.get(x, y - 1).symbol == "─" && buffer.get(x - 2, y).symbol == "│")
+        .map(|(x, y)| (x, y, buffer.get(x, y).bg))
+        .collect()
+}
+
+fn coloured(buffer: &Buffer, symbol: &str) -> usize {
+    tiles(buffer, symbol).iter().filter(|&&(_, _, bg)| bg != Color::Reset).count()
+}
+
+fn animated(session: &MultiSession) -> Screen {
+    let mut screen = Screen::new();
+    screen.animate = true;
+    screen.show(session);
+    screen
+}
+
+#[test]
+fn submitted_rows_flip_tile_by_tile() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    let mut screen = animated(&session);
+    assert!(!screen.animating(), "nothing to reveal in a new game");
+    session.submit_guess("CRAMP").unwrap();
+    screen.show(&session);
+    assert!(screen.animating());
+
+    // no tile is coloured before the first one turns over
+    assert_eq!(coloured(&render(&screen, ""), "C"), 0);
+    screen.advance(Duration::from_millis(100));
+    assert_eq!(coloured(&render(&screen, ""), "C"), 0, "the first tile is still turning");
+    screen.advance(Duration::from_millis(400));
+    let buffer = render(&screen, "");
+    assert_eq!(coloured(&buffer, "C"), 1, "the first tile has flipped");
+    assert_eq!(coloured(&buffer, "M"), 0, "the fourth tile has not");
+    assert_eq!(coloured(&buffer, "P"), 0, "the last tile has not");
+    screen.advance(Duration::from_millis(740));
+    assert!(screen.animating(), "five tiles take 1250ms");
+    screen.advance(Duration::from_millis(10));
+    assert!(!screen.animating());
+    assert_eq!(coloured(&render(&screen, ""), "P"), 1);
+
+    // a win bounces after the flips, so it lasts longer
+    session.submit_guess("CRANE").unwrap();
+    screen.show(&session);
+    screen.advance(Duration::from_millis(1300));
+    assert!(screen.animating());
+    screen.advance(Duration::from_millis(700));
+    assert!(!screen.animating());
+    let buffer = render(&screen, "");
+    let rows: Vec<u16> = "RANE".chars().map(|letter| tiles(&buffer, &letter.to_string()).iter().map(|&(_, y, _)| y).max().unwrap()).collect();
+    assert!(rows.iter().all(|&y| y == rows[0]), "every tile has landed: {:?}", rows);
+    assert_eq!(coloured(&buffer, "E"), 1);
+}
+
+#[test]
+fn invalid_words_shake_instead_of_showing_a_message() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    session.submit_guess("SLOTH").unwrap();
+    let mut screen = animated(&session);
+    screen.reject("CRAMX", "INVALID");
+    assert!(screen.animating());
+    assert!(screen.message.is_empty());
+    let (sx, sy) = find(&render(&screen, ""), "X")[0];
+    let (x, y) = find(&render(&screen, "CRAMX"), "X")[0];
+    assert_eq!(sy, y);
+    assert_eq!(sx, x + 1, "the rejected word starts one column to the right");
+    // and moves to the other side on the next frame
+    screen.advance(Duration::from_millis(30));
+    assert_eq!(find(&render(&screen, ""), "X")[0], (x - 1, y));
+    screen.advance(Duration::from_millis(370));
+    assert!(!screen.animating());
+    // typing a new word stops the shake
+    assert!(tiles(&render(&screen, "B"), "X").is_empty());
+
+    // other messages are still shown
+    screen.reject("SLOTH", "ALREADY GUESSED");
+    assert_eq!(screen.message, "ALREADY GUESSED");
+}
+
+#[test]
+fn animations_can_be_turned_off() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    let mut screen = Screen::new();
+    screen.show(&session);
+    session.submit_guess("CRAMP").unwrap();
+    screen.show(&session);
+    assert!(!screen.animating());
+    assert_eq!(coloured(&render(&screen, ""), "P"), 1, "the row is coloured at once");
+
+    screen.reject("CRAMX", "INVALID");
+    assert!(!screen.animating());
+    assert_eq!(screen.message, "INVALID");
+    assert!(tiles(&render(&screen, ""), "X").is_empty());
+}
+
+#[test]
+fn animations_are_skipped_when_the_boards_do_not_fit() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE"), Word::new("SLOTH")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    for guess in ["ABBEY", "BLOCK", "CHIRP", "DOUGH"] {
+        session.submit_guess(guess).unwrap();
+    }
+    let mut screen = animated(&session);
+    session.submit_guess("EIGHT").unwrap();
+    screen.show(&session);
+
+    // the scrolled grid shows EIGHT coloured on both boards, the full grid is still flipping it
+    let scrolled = render_at(&screen, 80, 24);
+    assert!(text(&scrolled).contains("ROWS 4-6"));
+    assert_eq!(coloured(&scrolled, "G"), 4);
+    assert_eq!(coloured(&render_at(&screen, 80, 40), "G"), 2);
+}
+
+// --no-animation only clears Screen::animate, so the game looks the same as it would once the animation is over
+#[test]
+fn unanimated_screens_match_finished_animations() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    let mut plain = Screen::new();
+    let mut screen = animated(&session);
+    session.submit_guess("CRAMP").unwrap();
+    plain.show(&session);
+    screen.show(&session);
+    plain.advance(Duration::from_millis(100));
+    assert!(!plain.animating());
+    assert_ne!(render(&plain, ""), render(&screen, ""));
+    screen.advance(Duration::from_millis(1250));
+    assert_eq!(render(&plain, ""), render(&screen, ""));
+}
+
+// a terminal too small for the game shows only the warning, and the animation carries on once it is resized
+#[test]
+fn tiny_terminals_do_not_draw_animations() {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    let mut screen = animated(&session);
+    session.submit_guess("CRAMP").unwrap();
+    screen.show(&session);
+    let tiny = render_at(&screen, 30, 10);
+    assert!(text(&tiny).contains("TERMINAL TOO SMALL"));
+    assert!(tiles(&tiny, "C").is_empty());
+    screen.advance(Duration::from_millis(500));
+    assert!(screen.animating());
+    assert_eq!(coloured(&render(&screen, ""), "C"), 1);
+    assert_eq!(coloured(&render(&screen, ""), "P"), 0);
+}
+
+static ENTERED: AtomicUsize = AtomicUsize::new(0);
+static RESTORED: AtomicUsize = AtomicUsize::new(0);
+
+fn fake_enter() -> std::io::Result<()> {
+    ENTERED.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+fn fake_restore() {
+    RESTORED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn the_terminal_is_restored_once_per_guard() {
+    let screen = Screen::new();
+    let mut terminal = Terminal::new(TestBackend::new(100, 34)).unwrap();
+    for _ in 0..3 {
+        let guard = TerminalGuard::with(fake_enter, fake_restore).unwrap();
+        terminal.draw(|f| screen.draw(f, "")).unwrap();
+        drop(guard);
+    }
+    assert_eq!((ENTERED.load(Ordering::SeqCst), RESTORED.load(Ordering::SeqCst)), (3, 3));
+
+    // a panic while drawing restores the terminal before the message is printed, and only once
+    let result = std::panic::catch_unwind(|| {
+        let _guard = TerminalGuard::with(fake_enter, fake_restore).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 34)).unwrap();
+        terminal.draw(|_| panic!("drawing failed")).unwrap();
+    });
+    assert!(result.is_err());
+    assert_eq!(RESTORED.load(Ordering::SeqCst), 4);
+
+    // the hook does nothing once every guard is gone, however many were entered
+    let _ = std::panic::catch_unwind(|| panic!("no guard"));
+    assert_eq!(RESTORED.load(Ordering::SeqCst), 4);
+}
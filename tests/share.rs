@@ -0,0 +1,53 @@
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::share::{self, ShareFormat};
+use wordle::{Difficulty, Theme, Word};
+
+fn played(answers: &[&str], guesses: &[&str]) -> MultiSession {
+    let answers = answers.iter().map(|answer| Word::new(answer)).collect();
+    let mut session = MultiSession::new(answers, &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    for guess in guesses {
+        session.submit_guess(guess).unwrap();
+    }
+    session
+}
+
+#[test]
+fn header_has_day_count_and_hard_mode_star() {
+    let session = played(&["CRANE"], &["SLATE", "CRANE"]);
+    let text = share::share_text(&session, Some(817), true, ShareFormat::Emoji, Theme::Default);
+    assert_eq!(text, "Wordle 817 2/6*\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩\n");
+
+    let text = share::share_text(&session, None, false, ShareFormat::Ascii, Theme::Default);
+    assert_eq!(text, "Wordle 2/6\n\n--G-G\nGGGGG\n");
+}
+
+#[test]
+fn failed_rounds_show_x() {
+    let session = played(&["CRANE"], &["SLATE", "SLATE", "SLATE", "SLATE", "SLATE", "TRACE"]);
+    let text = share::share_text(&session, Some(3), false, ShareFormat::Ascii, Theme::Default);
+    assert!(text.starts_with("Wordle 3 X/6\n"), "{}", text);
+    assert!(text.ends_with("-GGYG\n"), "{}", text);
+}
+
+#[test]
+fn colour_blind_theme_uses_orange_and_blue() {
+    let session = played(&["CRANE"], &["NACRE", "CRANE"]);
+    let text = share::share_text(&session, None, false, ShareFormat::Emoji, Theme::ColourBlind);
+    assert_eq!(text, "Wordle 2/6\n\n🟦🟦🟦🟦🟧\n🟧🟧🟧🟧🟧\n");
+}
+
+#[test]
+fn every_board_gets_its_own_grid() {
+    let session = played(&["CRANE", "SLATE"], &["CRANE", "SLATE"]);
+    let text = share::share_text(&session, None, false, ShareFormat::Ascii, Theme::Default);
+    assert_eq!(text, "Wordle 2/7\n\nGGGGG\n\n--G-G\nGGGGG\n");
+}
+
+#[test]
+fn formats_parse_and_clipboard_is_base64() {
+    assert_eq!("ASCII".parse::<ShareFormat>(), Ok(ShareFormat::Ascii));
+    assert_eq!("emoji".parse::<ShareFormat>(), Ok(ShareFormat::Emoji));
+    assert!("png".parse::<ShareFormat>().is_err());
+    assert_eq!(share::clipboard_sequence("Wordle 3/6"), "\x1b]52;c;V29yZGxlIDMvNg==\x07");
+    assert_eq!(share::clipboard_sequence("ab"), "\x1b]52;c;YWI=\x07");
+}
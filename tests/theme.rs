@@ -0,0 +1,58 @@
+use tui::backend::TestBackend;
+use tui::style::Color;
+use tui::Terminal;
+use wordle::screen::Screen;
+use wordle::session::{MultiSession, DEFAULT_MAX_GUESSES};
+use wordle::{Difficulty, LetterFeedback, Theme, Word};
+
+fn rendered(theme: Theme) -> (String, Vec<Color>) {
+    let mut session = MultiSession::new(vec![Word::new("CRANE")], &[], Difficulty::Normal, DEFAULT_MAX_GUESSES);
+    session.submit_guess("CLEAR").unwrap();
+    let mut screen = Screen::new();
+    screen.theme = theme;
+    screen.show(&session);
+    let mut terminal = Terminal::new(TestBackend::new(100, 34)).unwrap();
+    terminal.draw(|f| screen.draw(f, "")).unwrap();
+    let buffer = terminal.backend().buffer();
+    let text = buffer.content().iter().map(|cell| cell.symbol.clone()).collect();
+    let colors = buffer.content().iter().map(|cell| cell.bg).collect();
+    (text, colors)
+}
+
+#[test]
+fn names_round_trip() {
+    for theme in [Theme::Default, Theme::HighContrast, Theme::ColourBlind, Theme::Monochrome] {
+        assert_eq!(theme.name().parse::<Theme>(), Ok(theme));
+    }
+    assert_eq!("color-blind".parse::<Theme>(), Ok(Theme::ColourBlind));
+    assert_eq!("neon".parse::<Theme>(), Err("INVALID THEME".to_string()));
+}
+
+#[test]
+fn monochrome_uses_symbols_instead_of_colours() {
+    let theme = Theme::Monochrome;
+    assert_eq!(theme.mark('A', Some(LetterFeedback::Correct)), "[A]");
+    assert_eq!(theme.mark('A', Some(LetterFeedback::Present)), "(A)");
+    assert_eq!(theme.mark('A', Some(LetterFeedback::Absent)), " A ");
+    assert_eq!(theme.key_mark('A', Some(LetterFeedback::Absent)), " - ");
+    assert_eq!(Theme::Default.mark('A', Some(LetterFeedback::Correct)), "A");
+    for feedback in [LetterFeedback::Correct, LetterFeedback::Present, LetterFeedback::Absent] {
+        assert_eq!(theme.color(feedback), None);
+    }
+    assert_eq!(theme.hint(), None);
+
+    let (text, colors) = rendered(theme);
+    assert!(text.contains("[C]"), "{}", text);
+    assert!(text.contains("(R)"), "{}", text);
+    assert!(colors.iter().all(|color| *color == Color::Reset));
+}
+
+#[test]
+fn colour_blind_tiles_are_orange_and_blue() {
+    let (_, colors) = rendered(Theme::ColourBlind);
+    assert!(colors.contains(&Color::Rgb(245, 121, 58)));
+    assert!(colors.contains(&Color::Rgb(133, 192, 249)));
+    assert!(!colors.contains(&Color::Green));
+    // hints are not red either
+    assert_eq!(Theme::ColourBlind.hint(), Theme::ColourBlind.color(LetterFeedback::Correct));
+}
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use proptest::prelude::*;
+use rayon::prelude::*;
+use wordle::builtin_words::{ACCEPTABLE, FINAL};
+use wordle::feedback::LetterFeedback;
+use wordle::{Pattern, Word};
+
+// The previous HashMap-based Word::compare as a reference: the same two passes,
+// extended to any length and returning a Pattern instead of [u8; 5].
+fn reference_compare(answer: &str, guess: &str) -> Pattern {
+    let mut letter: HashMap<char, Vec<u8>> = HashMap::new();
+    for (count, tmp_letter) in answer.chars().enumerate() {
+        letter.entry(tmp_letter).or_default().push(count as u8);
+    }
+    let mut tmp_result = vec![LetterFeedback::Absent; answer.len()];
+    let mut answer_map = letter.clone();
+    for (count, i) in guess.chars().enumerate() {
+        match answer_map.get_mut(&i) {
+            Some(answer_vec) => {
+                tmp_result[count] = LetterFeedback::Present;
+                let t = answer_vec.clone();
+                for (j, k) in t.iter().enumerate() {
+                    if *k as usize == count {
+                        answer_vec.remove(j);
+                        tmp_result[count] = LetterFeedback::Correct;
+                    }
+                }
+            }
+            None => tmp_result[count] = LetterFeedback::Absent,
+        }
+    }
+    for (count, j) in guess.chars().enumerate() {
+        if tmp_result[count] != LetterFeedback::Present {
+            continue;
+        }
+        let answer_vec = answer_map.get_mut(&j).unwrap();
+        if answer_vec.is_empty() {
+            tmp_result[count] = LetterFeedback::Absent;
+            continue;
+        }
+        answer_vec.pop();
+    }
+    Pattern::new(&tmp_result)
+}
+
+// tens of millions of pairs, run with: cargo test --release -- --ignored
+#[test]
+#[ignore = "slow, compares every builtin pair"]
+fn matches_reference_for_all_builtin_pairs() {
+    FINAL.par_iter().for_each(|answer| {
+        for guess in ACCEPTABLE.iter() {
+            check_pair(answer, guess);
+        }
+    });
+}
+
+fn check_pair(answer: &str, guess: &str) {
+    let answer = answer.to_uppercase();
+    let guess = guess.to_uppercase();
+    let word = Word::new(&answer);
+    assert_eq!(word.compare(&guess), reference_compare(&answer, &guess), "answer {} guess {}", answer, guess);
+    assert_eq!(word.compare(&guess), word.compare_word(&Word::new(&guess)));
+}
+
+fn has_repeated_letter(word: &str) -> bool {
+    word.chars().enumerate().any(|(i, letter)| word.chars().skip(i + 1).any(|other| other == letter))
+}
+
+// A fixed sample of builtin pairs, so the default test run still compares real words.
+#[test]
+fn matches_reference_for_sampled_builtin_pairs() {
+    // 固定种子的线性同余生成器，每次运行抽取相同的组合
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = |bound: usize| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize % bound
+    };
+    for _ in 0..20000 {
+        check_pair(FINAL[next(FINAL.len())], ACCEPTABLE[next(ACCEPTABLE.len())]);
+    }
+
+    // Repeated letters on either side are where the two passes matter.
+    let answers: Vec<&str> = FINAL.iter().copied().filter(|word| has_repeated_letter(word)).step_by(7).collect();
+    let guesses: Vec<&str> = ACCEPTABLE.iter().copied().filter(|word| has_repeated_letter(word)).step_by(53).collect();
+    for answer in answers.iter() {
+        for guess in guesses.iter() {
+            check_pair(answer, guess);
+            check_pair(guess, answer);
+        }
+    }
+}
+
+#[test]
+fn repeated_letters() {
+    let cases = [
+        ("ABBEY", "BBXXX", "YGRRR"),
+        ("ABBEY", "BXBBX", "YRGRR"),
+        ("SPEED", "EERIE", "YYRRR"),
+        ("CRANE", "CRANE", "GGGGG"),
+        ("LLAMA", "ALLAY", "YGYYR"),
+    ];
+    for (answer, guess, expected) in cases {
+        assert_eq!(Word::new(answer).compare(guess).to_string(), expected, "{} {}", answer, guess);
+    }
+}
+
+proptest! {
+    // A three-letter alphabet forces plenty of repeated letters.
+    #[test]
+    fn matches_reference_on_small_alphabet(answer in "[ABC]{5}", guess in "[ABC]{5}") {
+        prop_assert_eq!(Word::new(&answer).compare(&guess), reference_compare(&answer, &guess));
+    }
+
+    #[test]
+    fn matches_reference_on_any_letters(answer in "[A-Z]{5}", guess in "[A-Z]{5}") {
+        prop_assert_eq!(Word::new(&answer).compare(&guess), reference_compare(&answer, &guess));
+    }
+
+    #[test]
+    fn matches_reference_on_other_lengths(
+        (answer, guess) in (2usize..=8).prop_flat_map(|length| {
+            let word = format!("[ABCD]{{{}}}", length);
+            (proptest::string::string_regex(&word).unwrap(), proptest::string::string_regex(&word).unwrap())
+        })
+    ) {
+        let pattern = Word::new(&answer).compare(&guess);
+        prop_assert_eq!(pattern.length(), answer.len());
+        prop_assert_eq!(pattern, reference_compare(&answer, &guess));
+        prop_assert_eq!(pattern.to_string().parse::<Pattern>(), Ok(pattern));
+    }
+
+    // Answers with Ä are compared letter by letter, the others with the A-Z count table;
+    // swapping Ä for C must not change any result.
+    #[test]
+    fn non_ascii_letters_match_reference(answer in "[ABÄ]{5}", guess in "[ABÄ]{5}") {
+        let ascii = |word: &str| word.replace('Ä', "C");
+        prop_assert_eq!(Word::new(&answer).compare(&guess), reference_compare(&ascii(&answer), &ascii(&guess)));
+        prop_assert_eq!(Word::new(&answer).compare_word(&Word::new(&guess)), Word::new(&answer).compare(&guess));
+    }
+}
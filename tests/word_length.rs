@@ -0,0 +1,19 @@
+use ntest::timeout;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_09_word_length() {
+    // six-letter words; the five-letter entry in the lists is filtered out
+    TestCase::read("09_01_word_length").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_09_solve_length() {
+    // the solver checks words and results against --length and suggests from the given lists
+    TestCase::read("09_02_solve_length").run_and_compare_result();
+}
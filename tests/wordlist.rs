@@ -0,0 +1,122 @@
+use ntest::timeout;
+use wordle::wordlist::{self, Filter, Issue, LetterStats, Problem, WordList};
+use wordle::WordleError;
+
+#[allow(dead_code)]
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_17_wordlist_check() {
+    // words of other lengths are listed but do not make the lists unusable
+    TestCase::read("17_01_wordlist_check").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_wordlist_check_bad() {
+    // a non-ASCII letter makes the list unusable
+    TestCase::read("17_02_wordlist_check_bad").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_acceptable_only() {
+    // a custom acceptable list is checked against the builtin answers
+    TestCase::read("17_03_acceptable_only").run_and_compare_result();
+}
+
+#[test]
+fn every_bad_line_is_reported() {
+    let text = "# comment\ncrane\n  slate  # opener\n\ncafé\ncranes\nCRANE\nabcdefghij\n";
+    let list = WordList::parse("list.txt", text, Some(5));
+    assert_eq!(list.words, vec!["CRANE", "SLATE"]);
+    let issue = |line: usize, word: &str, problem| Issue { line, word: word.to_string(), problem };
+    assert_eq!(
+        list.issues,
+        vec![
+            issue(5, "café", Problem::BadCharacter('é')),
+            issue(6, "cranes", Problem::OtherLength(6)),
+            issue(7, "CRANE", Problem::Duplicate(2)),
+            issue(8, "abcdefghij", Problem::TooLong(10)),
+        ]
+    );
+    let error = list.validate().unwrap_err();
+    assert!(matches!(error, WordleError::WordList { line: Some(5), .. }), "{:?}", error);
+    assert!(error.to_string().ends_with("(AND 1 MORE BAD LINES)"));
+}
+
+#[test]
+fn empty_lists_are_rejected() {
+    let list = WordList::parse("list.txt", "# only a comment\ncranes\n", Some(5));
+    assert!(list.errors().next().is_none());
+    assert!(list.validate().is_err());
+}
+
+#[test]
+fn subtracted_words_keep_their_order() {
+    let answers: Vec<String> = ["ZEBRA", "CRANE", "ABACK"].iter().map(|word| word.to_string()).collect();
+    let guesses: Vec<String> = ["CRANE", "SLATE"].iter().map(|word| word.to_string()).collect();
+    assert_eq!(wordlist::subtract(&answers, &guesses), vec!["ZEBRA", "ABACK"]);
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_wordlist_diff() {
+    TestCase::read("17_04_wordlist_diff").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_wordlist_filter() {
+    TestCase::read("17_05_wordlist_filter").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_wordlist_stats() {
+    TestCase::read("17_06_wordlist_stats").run_and_compare_result();
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|word| word.to_string()).collect()
+}
+
+#[test]
+fn merged_lists_are_sorted_without_duplicates() {
+    let merged = wordlist::merge(&[words(&["SLATE", "CRANE"]), words(&["CRANE", "ABACK"])]);
+    assert_eq!(merged, words(&["ABACK", "CRANE", "SLATE"]));
+}
+
+#[test]
+fn filters_combine_every_condition() {
+    let filter = Filter::new(Some("^s"), Some("s?a??"), Some("t"), Some("c")).unwrap();
+    assert!(filter.matches("SLATE"));
+    assert!(!filter.matches("SNACK"));
+    assert!(!filter.matches("SHAKE"));
+    assert!(!filter.matches("SLATES"));
+    assert!(Filter::new(None, Some("c1ane"), None, None).is_err());
+    assert!(Filter::new(Some("("), None, None, None).is_err());
+}
+
+#[test]
+fn letter_stats_count_positions_and_words() {
+    let stats = LetterStats::new(&words(&["EERIE", "CRANE"]));
+    assert_eq!(stats.words, 2);
+    let e = &stats.letters[0];
+    assert_eq!((e.letter, e.count, e.words), ('E', 4, 2));
+    assert_eq!(e.positions, vec![1, 1, 0, 0, 2]);
+}
+
+#[test]
+fn written_lists_read_back_unchanged() {
+    let path = std::env::temp_dir().join(format!("wordle-list-{}.txt", std::process::id()));
+    let list = WordList::open(wordlist::BUILTIN_FINAL, Some(5)).unwrap();
+    wordlist::write(Some(path.to_str().unwrap()), &list.words).unwrap();
+    let read = WordList::read(path.to_str().unwrap(), Some(5)).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(read.issues.is_empty());
+    assert_eq!(read.words.len(), list.words.len());
+    assert_eq!(wordlist::subtract(&list.words, &read.words), Vec::<String>::new());
+}